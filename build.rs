@@ -0,0 +1,5 @@
+// automod only sees the days present at build time, adding one must
+// trigger a rebuild
+fn main() {
+    println!("cargo:rerun-if-changed=src/days");
+}
@@ -0,0 +1,130 @@
+use crate::Result;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// answers/NN.txt holds the known-good answer of part 1 on the first line and
+// the one of part 2 on the second line, an empty line means unknown
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String, answer: String },
+    Missing,
+    Skipped,
+}
+
+impl Answers {
+    fn path(dir: &Path, day_number: u32) -> PathBuf {
+        dir.join(format!("{day_number:0>2}.txt"))
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut lines = content
+            .lines()
+            .map(|l| Some(l.trim()).filter(|l| !l.is_empty()).map(String::from));
+
+        Answers {
+            part1: lines.next().flatten(),
+            part2: lines.next().flatten(),
+        }
+    }
+
+    pub fn load(dir: &Path, day_number: u32) -> Result<Self> {
+        Self::read(&Self::path(dir, day_number))
+    }
+
+    pub fn save(&self, dir: &Path, day_number: u32) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        self.write(&Self::path(dir, day_number))
+    }
+
+    // a missing file means nothing is known
+    pub fn read(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(
+            path,
+            format!(
+                "{}\n{}\n",
+                self.part1.as_deref().unwrap_or(""),
+                self.part2.as_deref().unwrap_or("")
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    // the parts not run are skipped
+    pub fn check(&self, answers: &Answers) -> (Check, Check) {
+        (
+            check(&self.part1, &answers.part1),
+            check(&self.part2, &answers.part2),
+        )
+    }
+}
+
+fn check(expected: &Option<String>, answer: &Option<String>) -> Check {
+    match (expected, answer) {
+        (_, None) => Check::Skipped,
+        (None, _) => Check::Missing,
+        (Some(expected), Some(answer)) if expected == answer => Check::Pass,
+        (Some(expected), Some(answer)) => Check::Fail {
+            expected: expected.clone(),
+            answer: answer.clone(),
+        },
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail { expected, answer } => {
+                write!(f, "FAIL (expected {expected}, got {answer})")
+            }
+            Check::Missing => write!(f, "missing"),
+            Check::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+#[test]
+fn check_answers() {
+    let answers = Answers::parse("142\n\n");
+
+    assert_eq!(answers.part1.as_deref(), Some("142"));
+    assert_eq!(answers.part2, None);
+    assert_eq!(
+        answers.check(&Answers::parse("142\n281")),
+        (Check::Pass, Check::Missing)
+    );
+    assert_eq!(
+        answers.check(&Answers::parse("143\n281")),
+        (
+            Check::Fail {
+                expected: "142".to_string(),
+                answer: "143".to_string()
+            },
+            Check::Missing
+        )
+    );
+    // only part 2 was run
+    assert_eq!(
+        answers.check(&Answers::parse("\n281")),
+        (Check::Skipped, Check::Missing)
+    );
+    assert_eq!(Answers::parse(""), Answers::default());
+}
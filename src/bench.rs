@@ -0,0 +1,208 @@
+use crate::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Clone, Debug)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: usize,
+    // where to save the statistics as the new baseline
+    pub save: Option<PathBuf>,
+    // the baseline to compare with
+    pub baseline: Option<PathBuf>,
+    // the increase of a median, in percent, above which it is a regression
+    pub threshold: f64,
+}
+
+// durations are stored in nanoseconds
+fn serialize_ns<S: Serializer>(
+    d: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_u64(d.as_nanos() as u64)
+}
+
+fn deserialize_ns<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_nanos)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(serialize_with = "serialize_ns", deserialize_with = "deserialize_ns")]
+    pub min: Duration,
+    #[serde(serialize_with = "serialize_ns", deserialize_with = "deserialize_ns")]
+    pub median: Duration,
+    #[serde(serialize_with = "serialize_ns", deserialize_with = "deserialize_ns")]
+    pub mean: Duration,
+    #[serde(serialize_with = "serialize_ns", deserialize_with = "deserialize_ns")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    // samples is never empty, there is at least one run
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+// the statistics of parse, part1 and part2 of a day
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayBench {
+    pub day: u32,
+    pub phases: [Stats; 3],
+}
+
+impl DayBench {
+    // the relative change of each median against the baseline, if the day
+    // was benchmarked there
+    pub fn compare(&self, baseline: &[DayBench]) -> Option<[f64; 3]> {
+        let old = baseline.iter().find(|b| b.day == self.day)?;
+        let mut changes = [0.0; 3];
+
+        for (change, (new, old)) in changes
+            .iter_mut()
+            .zip(self.phases.iter().zip(old.phases.iter()))
+        {
+            let old = old.median.as_secs_f64();
+
+            *change = if old > 0.0 {
+                (new.median.as_secs_f64() - old) / old
+            } else {
+                0.0
+            };
+        }
+
+        Some(changes)
+    }
+}
+
+// the baseline file, the phases being named
+#[derive(Serialize, Deserialize)]
+struct Baseline {
+    runs: usize,
+    days: Vec<BaselineDay>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineDay {
+    day: u32,
+    parse: Stats,
+    part1: Stats,
+    part2: Stats,
+}
+
+pub fn save(path: &Path, runs: usize, benches: &[DayBench]) -> Result<()> {
+    let days = benches
+        .iter()
+        .map(|b| {
+            let [parse, part1, part2] = b.phases;
+
+            BaselineDay {
+                day: b.day,
+                parse,
+                part1,
+                part2,
+            }
+        })
+        .collect();
+    let baseline = serde_json::to_string(&Baseline { runs, days })
+        .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+
+    fs::write(path, format!("{baseline}\n"))?;
+
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Vec<DayBench>> {
+    let baseline: Baseline = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+
+    Ok(baseline
+        .days
+        .into_iter()
+        .map(|d| DayBench {
+            day: d.day,
+            phases: [d.parse, d.part1, d.part2],
+        })
+        .collect())
+}
+
+#[test]
+fn check_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::new(&[ms(4), ms(2), ms(8), ms(6)]);
+
+    assert_eq!(stats.min, ms(2));
+    assert_eq!(stats.median, ms(5));
+    assert_eq!(stats.mean, ms(5));
+    assert_eq!(stats.stddev.as_micros(), 2236);
+    assert_eq!(Stats::new(&[ms(3), ms(1), ms(2)]).median, ms(2));
+}
+
+#[test]
+fn check_baseline() {
+    let ms = Duration::from_millis;
+    let stats = |median| Stats {
+        min: ms(1),
+        median: ms(median),
+        mean: ms(median),
+        stddev: ms(0),
+    };
+    let path = crate::mock::temp_dir("bench").join("baseline.json");
+    let baseline = vec![DayBench {
+        day: 9,
+        phases: [stats(10), stats(20), stats(40)],
+    }];
+
+    save(&path, 10, &baseline).unwrap();
+
+    assert_eq!(load(&path).unwrap(), baseline);
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .starts_with(r#"{"runs":10,"days":[{"day":9,"parse":{"min":1000000,"#));
+
+    fs::write(&path, r#"{"runs":10,"days":[{"day":9}]}"#).unwrap();
+
+    assert!(matches!(load(&path), Err(Error::Config(_))));
+
+    let bench = DayBench {
+        day: 9,
+        phases: [stats(10), stats(30), stats(20)],
+    };
+
+    let percents = bench
+        .compare(&baseline)
+        .unwrap()
+        .map(|c| (c * 100.0).round());
+
+    assert_eq!(percents, [0.0, 50.0, -50.0]);
+    assert_eq!(DayBench { day: 1, ..bench }.compare(&baseline), None);
+}
@@ -0,0 +1,157 @@
+use crate::{Error, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "aoc.toml";
+
+// settings are taken from the defaults, then the config file, then the
+// AOC_* environment variables, the command line flags being applied last
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub base_url: String,
+    pub year: u32,
+    pub input_dir: PathBuf,
+    pub answers_dir: PathBuf,
+    pub examples_dir: PathBuf,
+    // defaults to a .cache directory in input_dir
+    pub cache_dir: Option<PathBuf>,
+    // minimum time between two requests to the server
+    pub request_interval: Duration,
+    pub session: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2023,
+            input_dir: PathBuf::from("./inputs"),
+            answers_dir: PathBuf::from("./answers"),
+            examples_dir: PathBuf::from("./puzzles"),
+            cache_dir: None,
+            request_interval: Duration::from_secs(5),
+            session: None,
+        }
+    }
+}
+
+impl Config {
+    // without an explicit path, AOC_CONFIG then ./aoc.toml are tried
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = Config::default();
+        let path = path
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("AOC_CONFIG").map(PathBuf::from))
+            .or_else(|| Some(PathBuf::from(CONFIG_FILE)).filter(|p| p.exists()));
+
+        if let Some(path) = path {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+
+            config
+                .apply_file(&content)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        }
+
+        for (key, var) in [
+            ("base_url", "AOC_BASE_URL"),
+            ("year", "AOC_YEAR"),
+            ("input_dir", "AOC_INPUT_DIR"),
+            ("answers_dir", "AOC_ANSWERS_DIR"),
+            ("examples_dir", "AOC_EXAMPLES_DIR"),
+            ("cache_dir", "AOC_CACHE_DIR"),
+            ("request_interval", "AOC_REQUEST_INTERVAL"),
+        ] {
+            if let Ok(value) = env::var(var) {
+                config
+                    .set(key, &value)
+                    .map_err(|e| Error::Config(format!("{var}: {e}")))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> std::result::Result<(), String> {
+        match key {
+            "base_url" => self.base_url = value.trim_end_matches('/').to_string(),
+            "year" => self.year = value.parse().map_err(|_| format!("invalid year {value}"))?,
+            "input_dir" => self.input_dir = PathBuf::from(value),
+            "answers_dir" => self.answers_dir = PathBuf::from(value),
+            "examples_dir" => self.examples_dir = PathBuf::from(value),
+            "cache_dir" => self.cache_dir = Some(PathBuf::from(value)),
+            "request_interval" => {
+                self.request_interval = value
+                    .parse()
+                    .ok()
+                    .and_then(|s| Duration::try_from_secs_f64(s).ok())
+                    .ok_or_else(|| format!("invalid interval {value}, expected seconds"))?
+            }
+            _ => return Err(format!("unknown setting {key}")),
+        }
+
+        Ok(())
+    }
+
+    // a flat TOML table, every value going through set like the environment
+    // variables do
+    fn apply_file(&mut self, content: &str) -> std::result::Result<(), String> {
+        let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+
+        for (key, value) in table {
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(n) => n.to_string(),
+                toml::Value::Float(n) => n.to_string(),
+                value => return Err(format!("unexpected {} for {key}", value.type_str())),
+            };
+
+            self.set(&key, &value)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir
+            .clone()
+            .unwrap_or_else(|| self.input_dir.join(".cache"))
+    }
+
+    pub fn input_path(&self, day_number: u32) -> PathBuf {
+        self.input_dir.join(format!("{day_number:0>2}.txt"))
+    }
+
+    pub fn day_url(&self, day_number: u32) -> String {
+        format!("{}/{}/day/{day_number}", self.base_url, self.year)
+    }
+}
+
+#[test]
+fn check_config_file() {
+    let mut config = Config::default();
+
+    config
+        .apply_file(
+            "# a mirror\nbase_url = \"http://localhost:8080/\"\nyear = 2022\n\ninput_dir = \"/tmp/aoc\"\nrequest_interval = 0.5\n",
+        )
+        .unwrap();
+
+    assert_eq!(config.base_url, "http://localhost:8080");
+    assert_eq!(config.year, 2022);
+    assert_eq!(config.input_path(5), Path::new("/tmp/aoc/05.txt"));
+    assert_eq!(config.cache_dir(), Path::new("/tmp/aoc/.cache"));
+    assert_eq!(config.request_interval, Duration::from_millis(500));
+    assert_eq!(config.day_url(5), "http://localhost:8080/2022/day/5");
+    assert_eq!(
+        config.apply_file("year = 2022\ncolor = \"blue\""),
+        Err("unknown setting color".to_string())
+    );
+    assert_eq!(
+        config.apply_file("year = [2022]"),
+        Err("unexpected array for year".to_string())
+    );
+    assert!(config.apply_file("input_dir = /tmp/aoc").is_err());
+}
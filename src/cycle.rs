@@ -0,0 +1,186 @@
+use crate::{check_deadline, Result};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// the states x0, x1 = step(x0), ... repeat from the mu-th with a period of
+// lambda, as any sequence over a finite number of states eventually does, the
+// deadline is checked while looking for it
+// https://en.wikipedia.org/wiki/Cycle_detection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub mu: usize,
+    pub lambda: usize,
+}
+
+impl Cycle {
+    // only two states are kept, for the states that are expensive to hash
+    pub fn floyd<S>(start: &S, mut step: impl FnMut(&mut S)) -> Result<Self>
+    where
+        S: Clone + PartialEq,
+    {
+        let mut tortoise = start.clone();
+        step(&mut tortoise);
+
+        let mut hare = tortoise.clone();
+        step(&mut hare);
+
+        while tortoise != hare {
+            check_deadline()?;
+            step(&mut tortoise);
+            step(&mut hare);
+            step(&mut hare);
+        }
+
+        // find the start of the loop
+        let mut mu = 0;
+        tortoise = start.clone();
+
+        while tortoise != hare {
+            check_deadline()?;
+            step(&mut tortoise);
+            step(&mut hare);
+            mu += 1;
+        }
+
+        // find the size of the loop
+        let mut lambda = 1;
+        hare = tortoise.clone();
+        step(&mut hare);
+
+        while tortoise != hare {
+            check_deadline()?;
+            step(&mut hare);
+            lambda += 1;
+        }
+
+        Ok(Cycle { mu, lambda })
+    }
+
+    // as floyd() but with less steps
+    pub fn brent<S>(start: &S, mut step: impl FnMut(&mut S)) -> Result<Self>
+    where
+        S: Clone + PartialEq,
+    {
+        // find the size of the loop, the tortoise waits for the hare at
+        // growing powers of two
+        let mut power = 1;
+        let mut lambda = 1;
+        let mut tortoise = start.clone();
+        let mut hare = start.clone();
+        step(&mut hare);
+
+        while tortoise != hare {
+            check_deadline()?;
+            if power == lambda {
+                tortoise = hare.clone();
+                power *= 2;
+                lambda = 0;
+            }
+            step(&mut hare);
+            lambda += 1;
+        }
+
+        // find the start of the loop, with the hare lambda steps ahead
+        let mut mu = 0;
+        tortoise = start.clone();
+        hare = start.clone();
+
+        for _ in 0..lambda {
+            step(&mut hare);
+        }
+
+        while tortoise != hare {
+            check_deadline()?;
+            step(&mut tortoise);
+            step(&mut hare);
+            mu += 1;
+        }
+
+        Ok(Cycle { mu, lambda })
+    }
+
+    // every state is kept, each is computed only once
+    pub fn hashed<S>(start: &S, mut step: impl FnMut(&mut S)) -> Result<Self>
+    where
+        S: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::new();
+        let mut state = start.clone();
+
+        for i in 0.. {
+            check_deadline()?;
+            match seen.entry(state.clone()) {
+                Entry::Occupied(entry) => {
+                    let mu = *entry.get();
+
+                    return Ok(Cycle { mu, lambda: i - mu });
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(i);
+                }
+            }
+            step(&mut state);
+        }
+        unreachable!()
+    }
+
+    // the first index of the state found after n steps
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+
+    // the state after n steps, without going around the loop
+    pub fn nth<S>(&self, start: &S, mut step: impl FnMut(&mut S), n: usize) -> S
+    where
+        S: Clone,
+    {
+        let mut state = start.clone();
+
+        for _ in 0..self.reduce(n) {
+            step(&mut state);
+        }
+        state
+    }
+}
+
+#[test]
+fn check_cycle() {
+    // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, 2...
+    let step = |n: &mut u32| *n = if *n < 5 { *n + 1 } else { 2 };
+    let expected = Cycle { mu: 2, lambda: 4 };
+
+    assert_eq!(Cycle::floyd(&0, step).unwrap(), expected);
+    assert_eq!(Cycle::brent(&0, step).unwrap(), expected);
+    assert_eq!(Cycle::hashed(&0, step).unwrap(), expected);
+    assert_eq!(expected.reduce(1), 1);
+    assert_eq!(expected.reduce(7), 3);
+    assert_eq!(expected.nth(&0, step, 1_000_000_000), 4);
+
+    // a fixed point from the start
+    assert_eq!(
+        Cycle::brent(&7, |_| ()).unwrap(),
+        Cycle { mu: 0, lambda: 1 }
+    );
+
+    // the three agree on a pseudo random sequence
+    let step = |n: &mut u64| *n = (*n * *n + 1) % 1009;
+
+    for start in [0, 3, 500] {
+        let cycle = Cycle::hashed(&start, step).unwrap();
+
+        assert_eq!(Cycle::floyd(&start, step).unwrap(), cycle);
+        assert_eq!(Cycle::brent(&start, step).unwrap(), cycle);
+
+        let mut n = start;
+
+        for _ in 0..5000 {
+            step(&mut n);
+        }
+        assert_eq!(cycle.nth(&start, step, 5000), n);
+    }
+}
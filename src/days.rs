@@ -1 +1,11 @@
 automod::dir!("src/days/");
+
+// every day against the examples extracted from the puzzle pages, in the
+// examples directory of the configuration
+#[test]
+fn check_examples() {
+    let config = advent_2023::Config::load(None).unwrap();
+    let failures = advent_2023::check_examples(&config.examples_dir);
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
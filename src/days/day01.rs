@@ -1,3 +1,4 @@
+use advent_2023::{Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
 fn calibration_value(line: &str) -> (u32, u32) {
@@ -8,7 +9,7 @@ fn calibration_value(line: &str) -> (u32, u32) {
     let (mut first_digit, mut last_digit) = (0, 0);
     let (mut first_digit_s, mut last_digit_s) = (None, None);
 
-    for (i, c) in line.chars().enumerate() {
+    for (i, c) in line.char_indices() {
         if c.is_ascii_digit() {
             first_digit = c as u32 - '0' as u32;
             break;
@@ -23,7 +24,7 @@ fn calibration_value(line: &str) -> (u32, u32) {
         }
     }
 
-    for (i, c) in line.chars().rev().enumerate() {
+    for (i, c) in line.char_indices().rev() {
         if c.is_ascii_digit() {
             last_digit = c as u32 - '0' as u32;
             break;
@@ -31,7 +32,7 @@ fn calibration_value(line: &str) -> (u32, u32) {
 
         if last_digit_s.is_none() {
             for (value_1, digit) in DIGIT_STRINGS.iter().enumerate() {
-                if line[line.len() - i - 1..].starts_with(digit) {
+                if line[i..].starts_with(digit) {
                     last_digit_s = Some(value_1 as u32 + 1);
                 }
             }
@@ -44,16 +45,31 @@ fn calibration_value(line: &str) -> (u32, u32) {
     )
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    lines.fold((0, 0), |(part1, part2), line| {
-        let line = line.unwrap();
-        let (c1, c2) = calibration_value(&line);
+struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        Ok(lines.collect::<std::io::Result<_>>()?)
+    }
+
+    fn part1(lines: &Self::Parsed) -> Result<u32> {
+        Ok(lines.iter().map(|line| calibration_value(line).0).sum())
+    }
+
+    fn part2(lines: &Self::Parsed) -> Result<u32> {
+        Ok(lines.iter().map(|line| calibration_value(line).1).sum())
+    }
 
-        (part1 + c1, part2 + c2)
-    })
+    fn validator() -> Validator {
+        Validator::new().alphabet("abcdefghijklmnopqrstuvwxyz0123456789")
+    }
 }
 
 #[test]
@@ -71,16 +87,26 @@ zoneight234
 7pqrstsixteen";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST1).lines()).0, 142);
-    assert_eq!(resolve(Cursor::new(TEST2).lines()).1, 281);
+    let test1 = Day01::parse(Cursor::new(TEST1).lines()).unwrap();
+    let test2 = Day01::parse(Cursor::new(TEST2).lines()).unwrap();
+
+    assert_eq!(Day01::part1(&test1).unwrap(), 142);
+    assert_eq!(Day01::part2(&test2).unwrap(), 281);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    use advent_2023::Error;
+
+    // a space in a line
+    assert!(matches!(
+        Day01::validator().check("1abc2\npqr3 stu8vwx"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(5),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day01>(file!()) }
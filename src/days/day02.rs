@@ -1,3 +1,4 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
 struct Bag {
@@ -6,49 +7,71 @@ struct Bag {
     b: u32,
 }
 
-fn bag_part(line: &str) -> (bool, u32) {
-    const BAG: Bag = Bag {
-        r: 12,
-        g: 13,
-        b: 14,
-    };
-    let games = line.split(": ").nth(1).unwrap();
-    let mut result = true;
+// the smallest bag allowing every draw of the game
+fn min_bag(index: usize, line: &str) -> Result<Bag> {
+    let games = line.split(": ").nth(1).at_line(index)?;
     let mut min_bag = Bag { r: 0, g: 0, b: 0 };
 
     for game in games.split("; ") {
         for color in game.split(", ") {
-            let split = color.split(' ').collect::<Vec<_>>();
-            let n = split[0].parse::<u32>().unwrap();
-            let (min_bag_n, bag_n) = match split[1] {
-                "red" => (&mut min_bag.r, BAG.r),
-                "green" => (&mut min_bag.g, BAG.g),
-                "blue" => (&mut min_bag.b, BAG.b),
-                _ => panic!(),
+            let (n, color) = color.split_once(' ').at_line(index)?;
+            let n = n.parse::<u32>().at_line(index)?;
+            let min_bag_n = match color {
+                "red" => &mut min_bag.r,
+                "green" => &mut min_bag.g,
+                "blue" => &mut min_bag.b,
+                _ => return Err(Error::parse(index, format!("unknown color {color}"))),
             };
 
             *min_bag_n = (*min_bag_n).max(n);
-
-            if n > bag_n {
-                result = false;
-            }
         }
     }
 
-    (result, min_bag.r * min_bag.g * min_bag.b)
+    Ok(min_bag)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    lines.enumerate().fold((0, 0), |(part1, part2), (i, line)| {
-        let line = line.unwrap();
-        let (ok, power) = bag_part(&line);
-        let index = if ok { i as u32 + 1 } else { 0 };
-
-        (part1 + index, part2 + power)
-    })
+struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Bag>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        lines
+            .enumerate()
+            .map(|(index, line)| min_bag(index, &line?))
+            .collect()
+    }
+
+    fn part1(games: &Self::Parsed) -> Result<u32> {
+        const BAG: Bag = Bag {
+            r: 12,
+            g: 13,
+            b: 14,
+        };
+
+        Ok(games
+            .iter()
+            .enumerate()
+            .filter(|(_, min_bag)| min_bag.r <= BAG.r && min_bag.g <= BAG.g && min_bag.b <= BAG.b)
+            .map(|(i, _)| i as u32 + 1)
+            .sum())
+    }
+
+    fn part2(games: &Self::Parsed) -> Result<u32> {
+        Ok(games
+            .iter()
+            .map(|min_bag| min_bag.r * min_bag.g * min_bag.b)
+            .sum())
+    }
+
+    fn validator() -> Validator {
+        Validator::new().lines(r"^Game \d+: \d+ (red|green|blue)((, |; )\d+ (red|green|blue))*$")
+    }
 }
 
 #[test]
@@ -60,15 +83,37 @@ Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (8, 2286));
+    assert_eq!(
+        Day02::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (8, 2286)
+    );
+}
+
+#[test]
+fn check_error() {
+    const TEST: &str = "Game 1: 3 blue, 4 red
+Game 2: 1 purple";
+    use std::io::Cursor;
+
+    assert_eq!(
+        Day02::resolve(Cursor::new(TEST).lines())
+            .unwrap_err()
+            .to_string(),
+        "parse error at line 2: unknown color purple"
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a color typo in the second game
+    assert!(matches!(
+        Day02::validator().check("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 gren"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day02>(file!()) }
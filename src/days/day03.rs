@@ -1,103 +1,138 @@
-use regex::Regex;
+use advent_2023::{Error, Grid, Point, Result, Solution, Validator, Vector};
+use std::collections::HashSet;
 use std::io::{BufRead, Lines};
 
 #[derive(Debug)]
 struct Number {
     value: u32,
-    a: (i32, i32),
-    b: (i32, i32),
+    start: Point,
+    length: usize,
 }
 
 impl Number {
-    fn new(value: u32, start: i32, end: i32, y: i32) -> Self {
-        let a = (start - 1, y - 1);
-        let b = (end, y + 1);
-
-        Number { value, a, b }
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.length).map(|i| self.start + Vector::new(i as i64, 0))
     }
 
-    fn contains(&self, (x, y): (i32, i32)) -> bool {
-        !(x < self.a.0 || x > self.b.0 || y < self.a.1 || y > self.b.1)
+    // the cells around the digits, each one once
+    fn neighbours(&self, grid: &Grid<u8>) -> HashSet<Point> {
+        self.points()
+            .flat_map(|p| grid.neighbours8(p))
+            .filter(|&p| !grid[p].is_ascii_digit())
+            .collect()
     }
+}
 
-    fn is_active(&self, points: &[(i32, i32)]) -> bool {
-        points.iter().any(|&p| self.contains(p))
-    }
+fn is_symbol(c: u8) -> bool {
+    c != b'.' && !c.is_ascii_digit()
 }
 
 #[test]
 fn check_number() {
-    let n = Number::new(123, 2, 5, 3);
-
-    assert!(!n.contains((2, 1)));
-    assert!(n.contains((2, 2)));
-    assert!(n.contains((2, 3)));
-    assert!(n.contains((2, 4)));
-    assert!(!n.contains((2, 5)));
-
-    assert!(!n.contains((0, 3)));
-    assert!(n.contains((2, 3)));
-    assert!(n.contains((5, 3)));
-    assert!(!n.contains((6, 3)));
-}
-
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    let re = Regex::new(r"(?<number>\d+)|(?<symbol>[^\.])").unwrap();
-    let mut numbers = vec![];
-    let mut symbols = vec![];
-    let mut asterix = vec![];
+    use std::io::Cursor;
 
-    for (y, line) in lines.enumerate() {
-        let line = line.unwrap();
+    let grid = Grid::parse(Cursor::new(".....\n.123.\n....*").lines(), Some).unwrap();
+    let n = Number {
+        value: 123,
+        start: Point::new(1, 1),
+        length: 3,
+    };
 
-        for capture in re.captures_iter(&line) {
-            if let Some(m) = capture.name("number") {
-                let n = m.as_str().parse::<u32>().unwrap();
+    assert_eq!(n.neighbours(&grid).len(), 12);
+    assert!(n.neighbours(&grid).contains(&Point::new(4, 2)));
+    assert!(!n.neighbours(&grid).contains(&Point::new(2, 1)));
+}
 
-                numbers.push(Number::new(n, m.start() as i32, m.end() as i32, y as i32));
-            } else if let Some(m) = capture.name("symbol") {
-                symbols.push((m.start() as i32, y as i32));
+struct Schematic {
+    grid: Grid<u8>,
+    numbers: Vec<Number>,
+}
 
-                if m.as_str() == "*" {
-                    asterix.push((m.start() as i32, y as i32));
+struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Schematic;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let grid = Grid::parse(lines, Some)?;
+        let mut numbers = vec![];
+
+        for (y, row) in grid.rows().enumerate() {
+            let mut x = 0;
+
+            while x < row.len() {
+                let length = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+                if length > 0 {
+                    let value = row[x..x + length]
+                        .iter()
+                        .try_fold(0u32, |n, c| n.checked_mul(10)?.checked_add((c - b'0') as u32))
+                        .ok_or_else(|| Error::parse(y, "number too large"))?;
+
+                    numbers.push(Number {
+                        value,
+                        start: Point::from((x, y)),
+                        length,
+                    });
                 }
-            } else {
-                panic!();
+                x += length.max(1);
             }
         }
+
+        Ok(Schematic { grid, numbers })
     }
 
-    let part1 = numbers
-        .iter()
-        .filter_map(|n| {
-            if n.is_active(&symbols) {
-                Some(n.value)
-            } else {
-                None
-            }
-        })
-        .sum();
-
-    let part2 = asterix
-        .iter()
-        .filter_map(|&a| {
-            let values = numbers
-                .iter()
-                .filter_map(|n| if n.contains(a) { Some(n.value) } else { None })
-                .collect::<Vec<_>>();
-
-            if values.len() > 1 {
-                Some(values.iter().product::<u32>())
-            } else {
-                None
-            }
-        })
-        .sum();
+    fn part1(schematic: &Self::Parsed) -> Result<u32> {
+        let grid = &schematic.grid;
+        let part1 = schematic
+            .numbers
+            .iter()
+            .filter(|n| n.neighbours(grid).into_iter().any(|p| is_symbol(grid[p])))
+            .map(|n| n.value)
+            .sum();
+
+        Ok(part1)
+    }
+
+    fn part2(schematic: &Self::Parsed) -> Result<u32> {
+        let grid = &schematic.grid;
+        let neighbours = schematic
+            .numbers
+            .iter()
+            .map(|n| n.neighbours(grid))
+            .collect::<Vec<_>>();
+        let part2 = grid
+            .iter()
+            .filter(|&(_, &c)| c == b'*')
+            .filter_map(|(a, _)| {
+                let values = schematic
+                    .numbers
+                    .iter()
+                    .zip(&neighbours)
+                    .filter_map(|(n, around)| around.contains(&a).then_some(n.value))
+                    .collect::<Vec<_>>();
+
+                if values.len() > 1 {
+                    Some(values.iter().product::<u32>())
+                } else {
+                    None
+                }
+            })
+            .sum();
 
-    (part1, part2)
+        Ok(part2)
+    }
+
+    fn validator() -> Validator {
+        Validator::new()
+            .grid()
+            .alphabet(r##"0123456789.!"#$%&'()*+,-/:;<=>?@[\]^_`{|}~"##)
+    }
 }
 
 #[test]
@@ -114,15 +149,23 @@ fn check() {
 .664.598..";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (4361, 467835));
+    assert_eq!(
+        Day03::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (4361, 467835)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a row one column short
+    assert!(matches!(
+        Day03::validator().check("467..114..\n...*.....\n..35..633."),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(10),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day03>(file!()) }
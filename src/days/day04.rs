@@ -1,32 +1,51 @@
+use advent_2023::{AtLine, Result, Solution, Validator};
 use regex::Regex;
 use std::collections::VecDeque;
 use std::io::{BufRead, Lines};
 
-fn resolve<T>(lines: Lines<T>) -> (u32, usize)
-where
-    T: BufRead,
-{
-    let re = Regex::new(r"^Card\s+(\d+): ([\d ]+) \| ([\d ]+)$").unwrap();
-    let mut copies = VecDeque::new();
-    let mut part2 = 0;
-
-    let part1 = lines
-        .filter_map(|line| {
-            let line = line.unwrap();
-
-            let m = re.captures(&line).unwrap();
-            let winning_numbers = m
-                .get(2)
-                .unwrap()
-                .as_str()
-                .split_whitespace()
-                .collect::<Vec<_>>();
-            let own_numbers = m.get(3).unwrap().as_str().split_whitespace();
-
-            let n = own_numbers
-                .filter(|&own| winning_numbers.contains(&own))
-                .count();
+struct Day04;
 
+impl Solution for Day04 {
+    // the count of winning numbers of each card
+    type Parsed = Vec<usize>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let re = Regex::new(r"^Card\s+(\d+): ([\d ]+) \| ([\d ]+)$").unwrap();
+
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line?;
+
+                let m = re.captures(&line).at_line(index)?;
+                let winning_numbers = m[2].split_whitespace().collect::<Vec<_>>();
+                let own_numbers = m[3].split_whitespace();
+
+                Ok(own_numbers
+                    .filter(|&own| winning_numbers.contains(&own))
+                    .count())
+            })
+            .collect()
+    }
+
+    fn part1(cards: &Self::Parsed) -> Result<u32> {
+        Ok(cards
+            .iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| 2u32.pow(n as u32 - 1))
+            .sum())
+    }
+
+    fn part2(cards: &Self::Parsed) -> Result<usize> {
+        let mut copies = VecDeque::new();
+        let mut part2 = 0;
+
+        for &n in cards {
             let copies_1 = 1 + copies.pop_front().unwrap_or(0);
 
             part2 += copies_1;
@@ -38,16 +57,14 @@ where
             for copy in copies.iter_mut().take(n) {
                 *copy += copies_1;
             }
+        }
 
-            if n == 0 {
-                None
-            } else {
-                Some(2u32.pow(n as u32 - 1))
-            }
-        })
-        .sum();
+        Ok(part2)
+    }
 
-    (part1, part2)
+    fn validator() -> Validator {
+        Validator::new().lines(r"^Card +\d+:( +\d+)+ \|( +\d+)+$")
+    }
 }
 
 #[test]
@@ -60,15 +77,22 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (13, 30));
+    assert_eq!(Day04::resolve(Cursor::new(TEST).lines()).unwrap(), (13, 30));
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    use advent_2023::Error;
+
+    // the bar separating the numbers is missing
+    assert!(matches!(
+        Day04::validator().check("Card 1: 41 48 83 | 83 86  6\nCard 2: 13 32 20 61 30 68"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day04>(file!()) }
@@ -1,75 +1,10 @@
-use advent_2023::Paragrapher;
-use std::cmp::Ordering;
+use advent_2023::{Error, Interval, IntervalSet, Paragrapher, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-#[derive(Debug, Eq)]
-struct Range {
-    start: i64,
-    end: i64,
-}
-
-impl Range {
-    fn new(start: i64, length: i64) -> Self {
-        let end = start + length;
-
-        Range { start, end }
-    }
-}
-
-impl PartialOrd for Range {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Range {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.start.cmp(&other.start)
-    }
-}
-
-impl PartialEq for Range {
-    fn eq(&self, other: &Self) -> bool {
-        self.start.eq(&other.start)
-    }
-}
-
-#[derive(Debug, Eq)]
-struct Translation {
-    range: Range,
-    value: i64,
-}
-
-impl Translation {
-    fn new(destination: i64, source: i64, size: i64) -> Self {
-        let value = destination - source;
-        let range = Range::new(source, size);
-
-        Translation { range, value }
-    }
-}
-
-impl PartialOrd for Translation {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Translation {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.range.cmp(&other.range)
-    }
-}
-
-impl PartialEq for Translation {
-    fn eq(&self, other: &Self) -> bool {
-        self.range.eq(&other.range)
-    }
-}
-
+// the source intervals of a map and the offset to their destinations
 #[derive(Debug)]
 struct Maps {
-    translations: Vec<Translation>,
+    translations: Vec<(Interval<i64>, i64)>,
 }
 
 impl Maps {
@@ -79,164 +14,138 @@ impl Maps {
         }
     }
 
-    fn found(&self, value: i64) -> Option<&Translation> {
-        self.translations
-            .iter()
-            .find(|t| (value >= t.range.start) && (value < t.range.end))
-    }
+    fn add(&mut self, destination: i64, source: i64, size: i64) {
+        let range = Interval::new(source, source + size);
 
-    fn add_sorted(&mut self, destination: i64, source: i64, size: i64) {
-        let t = Translation::new(destination, source, size);
-        let pos = self.translations.binary_search(&t).unwrap_or_else(|e| e);
-
-        self.translations.insert(pos, t);
+        self.translations.push((range, destination - source));
     }
 
     fn part1(&self, seeds: &mut [i64]) {
         for s in seeds {
-            let found = self.found(*s);
+            let found = self.translations.iter().find(|(r, _)| r.contains(*s));
 
-            if let Some(t) = found {
-                *s += t.value;
+            if let Some((_, value)) = found {
+                *s += value;
             }
         }
     }
 
-    fn part2_range(&self, r: &Range) -> Vec<Range> {
-        let (mut start, mut end) = (r.start, r.end);
-        let mut ranges = vec![];
+    fn part2(&self, ranges: &IntervalSet<i64>) -> IntervalSet<i64> {
+        ranges.map(&self.translations)
+    }
+}
 
-        for t in self.translations.iter() {
-            if end < t.range.start {
-                break;
-            }
+#[test]
+fn check_maps() {
+    let mut maps: Maps = Maps::new();
 
-            if start > t.range.end {
-                continue;
-            }
+    maps.add(50, 98, 2);
+    maps.add(52, 50, 48);
 
-            if start < t.range.start {
-                ranges.push(Range {
-                    start,
-                    end: t.range.start,
-                });
+    let mut seeds = [79, 14, 98, 55];
+    maps.part1(&mut seeds);
+    assert_eq!(seeds, [81, 14, 50, 57]);
 
-                start = t.range.start;
-            }
+    let ranges = IntervalSet::from_iter([Interval::new(45, 55), Interval::new(99, 101)]);
+    assert_eq!(
+        maps.part2(&ranges).intervals(),
+        [
+            Interval::new(45, 50),
+            Interval::new(51, 57),
+            Interval::new(100, 101)
+        ]
+    );
+}
 
-            if end < t.range.end {
-                start += t.value;
-                end += t.value;
-                break;
-            } else {
-                ranges.push(Range {
-                    start: start + t.value,
-                    end: t.range.end + t.value,
-                });
-
-                start = t.range.end;
+struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<Maps>,
+}
+
+struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Almanac;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<T>(mut lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let mut para_iter = lines.split_paragraph(|s| {
+            s.split_whitespace()
+                .filter_map(|s| s.parse::<i64>().ok())
+                .collect::<Vec<_>>()
+        });
+
+        let seeds = match para_iter.next().transpose()?.as_deref() {
+            Some([seeds]) => seeds.clone(),
+            _ => return Err(Error::parse(0, "missing seeds")),
+        };
+        let mut almanac = Almanac {
+            seeds,
+            maps: vec![],
+        };
+
+        // seeds line and its trailing empty line
+        let mut index = 2;
+
+        for p in para_iter {
+            let p = p?;
+            let mut maps = Maps::new();
+
+            for (i, numbers) in p.iter().enumerate().skip(1) {
+                match numbers[..] {
+                    [destination, source, size] => maps.add(destination, source, size),
+                    _ => return Err(Error::parse(index + i, "expected 3 numbers")),
+                }
             }
-        }
-
-        ranges.push(Range { start, end });
-        ranges
-    }
 
-    fn part2(&self, ranges: &[Range]) -> Vec<Range> {
-        let mut ret = vec![];
+            index += p.len() + 1;
 
-        for r in ranges {
-            for result in self.part2_range(r) {
-                ret.push(result);
-            }
+            almanac.maps.push(maps);
         }
 
-        ret
+        Ok(almanac)
     }
-}
-
-#[test]
-fn check_range() {
-    let mut maps: Maps = Maps::new();
 
-    maps.add_sorted(50, 98, 2);
-    maps.add_sorted(52, 50, 48);
+    fn part1(almanac: &Self::Parsed) -> Result<i64> {
+        let mut seeds = almanac.seeds.clone();
 
-    let range = Range::new(3, 2);
-    assert_eq!(maps.part2_range(&range), [Range { start: 3, end: 5 }]);
-
-    let range = Range::new(55, 5);
-    assert_eq!(maps.part2_range(&range), [Range { start: 57, end: 62 }]);
-
-    let range = Range::new(45, 10);
-    assert_eq!(
-        maps.part2_range(&range),
-        [Range { start: 45, end: 50 }, Range { start: 52, end: 57 }]
-    );
+        for maps in almanac.maps.iter() {
+            maps.part1(&mut seeds);
+        }
 
-    let range = Range::new(99, 2);
-    assert_eq!(
-        maps.part2_range(&range),
-        [
-            Range { start: 51, end: 52 },
-            Range {
-                start: 100,
-                end: 101
-            }
-        ]
-    );
+        seeds
+            .into_iter()
+            .min()
+            .ok_or_else(|| Error::unsolvable("no seeds"))
+    }
 
-    let range = Range::new(45, 150);
-    assert_eq!(
-        maps.part2_range(&range),
-        [
-            Range { start: 45, end: 50 },
-            Range {
-                start: 52,
-                end: 100
-            },
-            Range { start: 50, end: 52 },
-            Range {
-                start: 100,
-                end: 205
-            },
-        ]
-    );
-}
+    fn part2(almanac: &Self::Parsed) -> Result<i64> {
+        let mut seeds_ranges = almanac
+            .seeds
+            .iter()
+            .step_by(2)
+            .zip(almanac.seeds.iter().skip(1).step_by(2))
+            .map(|(&start, &length)| Interval::new(start, start + length))
+            .collect::<IntervalSet<_>>();
 
-fn resolve<T>(mut lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
-    let mut para_iter = lines.split_paragraph(|s| {
-        s.split_whitespace()
-            .filter_map(|s| s.parse::<i64>().ok())
-            .collect::<Vec<_>>()
-    });
-
-    let mut seeds = para_iter.next().unwrap()[0].clone();
-    let mut seeds_ranges = seeds
-        .iter()
-        .step_by(2)
-        .zip(seeds.iter().skip(1).step_by(2))
-        .map(|(&start, &length)| Range::new(start, length))
-        .collect::<Vec<_>>();
-
-    for p in para_iter {
-        let mut maps = Maps::new();
-
-        for numbers in p.into_iter().skip(1) {
-            assert_eq!(numbers.len(), 3);
-            maps.add_sorted(numbers[0], numbers[1], numbers[2]);
+        for maps in almanac.maps.iter() {
+            seeds_ranges = maps.part2(&seeds_ranges);
         }
 
-        maps.part1(&mut seeds);
-        seeds_ranges = maps.part2(&seeds_ranges);
+        seeds_ranges
+            .min()
+            .ok_or_else(|| Error::unsolvable("no seeds ranges"))
     }
 
-    seeds_ranges.sort_unstable();
-
-    (*seeds.iter().min().unwrap(), seeds_ranges[0].start)
+    fn validator() -> Validator {
+        Validator::new()
+            .paragraphs()
+            .lines(r"^(seeds:( \d+)+|[a-z]+-to-[a-z]+ map:|\d+ \d+ \d+)$")
+    }
 }
 
 #[test]
@@ -276,15 +185,20 @@ humidity-to-location map:
 56 93 4";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (35, 46));
-}
-
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+    assert_eq!(Day05::resolve(Cursor::new(TEST).lines()).unwrap(), (35, 46));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+#[test]
+fn check_validator() {
+    // a map line with a missing number
+    assert!(matches!(
+        Day05::validator().check("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50"),
+        Err(Error::Invalid {
+            line: Some(5),
+            column: None,
+            ..
+        })
+    ));
+}
+
+inventory::submit! { advent_2023::Day::new::<Day05>(file!()) }
@@ -1,3 +1,4 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::io::{BufRead, Lines};
 
@@ -52,47 +53,69 @@ fn winners(race: &Race) -> i64 {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
-    let mut lines = lines;
-    let (mut total_time, mut total_distance) = (String::new(), String::new());
-    let mut races = lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|time| {
-            total_time.push_str(time);
-
-            Race {
-                time: time.parse::<i64>().unwrap(),
-                distance: 0,
-            }
-        })
-        .collect::<Vec<_>>();
-
-    lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .enumerate()
-        .for_each(|(idx, distance)| {
+struct Day06;
+
+impl Solution for Day06 {
+    // the races and the single race of part 2, kerning removed
+    type Parsed = (Vec<Race>, Race);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let mut lines = lines;
+        let (mut total_time, mut total_distance) = (String::new(), String::new());
+        let mut races = lines
+            .next()
+            .at_line(0)??
+            .split_whitespace()
+            .skip(1)
+            .map(|time| {
+                total_time.push_str(time);
+
+                Ok(Race {
+                    time: time.parse::<i64>().at_line(0)?,
+                    distance: 0,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (idx, distance) in lines
+            .next()
+            .at_line(1)??
+            .split_whitespace()
+            .skip(1)
+            .enumerate()
+        {
             total_distance.push_str(distance);
 
-            races[idx].distance = distance.parse::<i64>().unwrap();
-        });
+            races
+                .get_mut(idx)
+                .ok_or_else(|| Error::parse(1, "more distances than times"))?
+                .distance = distance.parse::<i64>().at_line(1)?;
+        }
+
+        let total_race = Race {
+            time: total_time.parse::<i64>().at_line(0)?,
+            distance: total_distance.parse::<i64>().at_line(1)?,
+        };
+
+        Ok((races, total_race))
+    }
+
+    fn part1((races, _): &Self::Parsed) -> Result<i64> {
+        Ok(races.iter().map(winners).product())
+    }
 
-    let total_race = Race {
-        time: total_time.parse::<i64>().unwrap(),
-        distance: total_distance.parse::<i64>().unwrap(),
-    };
+    fn part2((_, total_race): &Self::Parsed) -> Result<i64> {
+        Ok(winners(total_race))
+    }
 
-    (races.iter().map(winners).product(), winners(&total_race))
+    fn validator() -> Validator {
+        Validator::new().lines(r"^(Time|Distance):( +\d+)+$")
+    }
 }
 
 #[test]
@@ -101,15 +124,23 @@ fn check() {
 Distance:  9  40  200";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (288, 71503));
+    assert_eq!(
+        Day06::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (288, 71503)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a letter O instead of a zero
+    assert!(matches!(
+        Day06::validator().check("Time:      7  15   30\nDistance:  9  40  2O0"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day06>(file!()) }
@@ -1,3 +1,4 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::cmp::{Ordering, Ordering::Equal};
 use std::io::{BufRead, Lines};
 
@@ -9,10 +10,14 @@ fn card_value(c: char) -> u32 {
         'J' => 11,
         'T' => 10,
         '2'..='9' => c as u32 - '0' as u32,
-        _ => panic!(""),
+        _ => unreachable!(),
     }
 }
 
+fn is_valid_hand(hand: &str) -> bool {
+    hand.len() == 5 && hand.chars().all(|c| "AKQJT23456789".contains(c))
+}
+
 fn get_type(cards: &[(u32, u32)]) -> u32 {
     match cards.len() {
         1 => 7,
@@ -32,7 +37,7 @@ fn get_type(cards: &[(u32, u32)]) -> u32 {
         }
         4 => 2,
         5 => 1,
-        _ => panic!(""),
+        _ => unreachable!(),
     }
 }
 
@@ -222,40 +227,57 @@ struct Hand {
     part2: HandValue,
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    let mut hands = vec![];
-
-    for line in lines {
-        let line = line.unwrap();
-        let words = line.split_whitespace().collect::<Vec<_>>();
-
-        hands.push(Hand {
-            bid: words[1].parse::<u32>().unwrap(),
-            part1: HandValue::part1(words[0]),
-            part2: HandValue::part2(words[0]),
-        });
-    }
-
-    hands.sort_unstable_by_key(|hand| hand.part1);
-
-    let part1 = hands
+// the bids ordered by the value of their hands
+fn winnings(mut hands: Vec<(HandValue, u32)>) -> u32 {
+    hands.sort_unstable_by_key(|&(value, _)| value);
+    hands
         .iter()
         .enumerate()
-        .map(|(idx, hand)| hand.bid * (idx as u32 + 1))
-        .sum();
+        .map(|(idx, (_, bid))| bid * (idx as u32 + 1))
+        .sum()
+}
 
-    hands.sort_unstable_by_key(|hand| hand.part2);
+struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<Hand>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line?;
+                let (cards, bid) = line.split_once(' ').at_line(index)?;
+
+                if !is_valid_hand(cards) {
+                    return Err(Error::parse(index, format!("invalid hand {cards}")));
+                }
 
-    let part2 = hands
-        .iter()
-        .enumerate()
-        .map(|(idx, hand)| hand.bid * (idx as u32 + 1))
-        .sum();
+                Ok(Hand {
+                    bid: bid.parse::<u32>().at_line(index)?,
+                    part1: HandValue::part1(cards),
+                    part2: HandValue::part2(cards),
+                })
+            })
+            .collect()
+    }
+
+    fn part1(hands: &Self::Parsed) -> Result<u32> {
+        Ok(winnings(hands.iter().map(|h| (h.part1, h.bid)).collect()))
+    }
+
+    fn part2(hands: &Self::Parsed) -> Result<u32> {
+        Ok(winnings(hands.iter().map(|h| (h.part2, h.bid)).collect()))
+    }
 
-    (part1, part2)
+    fn validator() -> Validator {
+        Validator::new().lines(r"^[AKQJT2-9]{5} \d+$")
+    }
 }
 
 #[test]
@@ -267,15 +289,23 @@ KTJJT 220
 QQQJA 483";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (6440, 5905));
+    assert_eq!(
+        Day07::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (6440, 5905)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a lowercase card
+    assert!(matches!(
+        Day07::validator().check("32T3K 765\nt55J5 684"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day07>(file!()) }
@@ -1,3 +1,4 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::collections::HashMap;
 use std::io::{BufRead, Lines};
 
@@ -26,12 +27,19 @@ fn check_lcm() {
     assert_eq!(lcm(1, 23456), 23456);
 }
 
-fn compute(path: &[(usize, usize)], start: usize, ends: &[usize], sequence: &[bool]) -> usize {
+// after every (node, sequence_index) state the walk is looping without
+// reaching an end
+fn compute(
+    path: &[(usize, usize)],
+    start: usize,
+    ends: &[usize],
+    sequence: &[bool],
+) -> Result<usize> {
     let mut c = start;
     let mut counter = 1;
     let mut sequence_index = 0;
 
-    loop {
+    while counter <= path.len() * sequence.len() {
         c = if sequence[sequence_index] {
             path[c].0
         } else {
@@ -39,88 +47,142 @@ fn compute(path: &[(usize, usize)], start: usize, ends: &[usize], sequence: &[bo
         };
 
         if ends.contains(&c) {
-            return counter;
+            return Ok(counter);
         }
 
         counter += 1;
         sequence_index = (sequence_index + 1) % sequence.len();
     }
+
+    Err(Error::unsolvable("the end is never reached"))
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut lines = lines;
-    let sequence = lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .chars()
-        .map(|c| match c {
-            'L' => true,
-            'R' => false,
-            _ => panic!(""),
-        })
-        .collect::<Vec<_>>();
-    let mut starting_z = vec![];
-    let mut ending_z = vec![];
-    let (mut index_aaa, mut index_zzz) = (None, None);
+struct Network {
+    sequence: Vec<bool>,
+    path: Vec<(usize, usize)>,
+    starting_z: Vec<usize>,
+    ending_z: Vec<usize>,
+    index_aaa: Option<usize>,
+    index_zzz: Option<usize>,
+}
 
-    lines.next();
+struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Network;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let mut lines = lines;
+        let sequence = lines
+            .next()
+            .at_line(0)??
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(true),
+                'R' => Ok(false),
+                _ => Err(Error::parse(0, format!("invalid instruction {c}"))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if sequence.is_empty() {
+            return Err(Error::parse(0, "no instructions"));
+        }
+        let mut starting_z = vec![];
+        let mut ending_z = vec![];
+        let (mut index_aaa, mut index_zzz) = (None, None);
+
+        lines.next();
 
-    let mut graph = HashMap::new();
+        let mut graph = HashMap::new();
 
-    for line in lines {
-        let line = line.unwrap();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
 
-        let name = &line[0..3];
-        let left = &line[7..10];
-        let right = &line[12..15];
+            let (name, left, right) = match (line.get(0..3), line.get(7..10), line.get(12..15)) {
+                (Some(name), Some(left), Some(right)) => (name, left, right),
+                // the two first lines were consumed above
+                _ => return Err(Error::parse(index + 2, "expected `AAA = (BBB, CCC)`")),
+            };
 
-        if &line[2..3] == "A" {
-            starting_z.push(graph.len());
+            if &line[2..3] == "A" {
+                starting_z.push(graph.len());
 
-            if &line[0..2] == "AA" {
-                index_aaa = Some(graph.len());
+                if &line[0..2] == "AA" {
+                    index_aaa = Some(graph.len());
+                }
             }
-        }
-        if &line[2..3] == "Z" {
-            ending_z.push(graph.len());
+            if &line[2..3] == "Z" {
+                ending_z.push(graph.len());
 
-            if &line[0..2] == "ZZ" {
-                index_zzz = Some(graph.len());
+                if &line[0..2] == "ZZ" {
+                    index_zzz = Some(graph.len());
+                }
             }
+
+            graph.insert(
+                name.to_string(),
+                (graph.len(), left.to_string(), right.to_string()),
+            );
         }
 
-        graph.insert(
-            name.to_string(),
-            (graph.len(), left.to_string(), right.to_string()),
-        );
-    }
+        let mut path = vec![(0, 0); graph.len()];
 
-    let mut path = vec![(0, 0); graph.len()];
+        for (index, l, r) in graph.values() {
+            let node = |n: &String| {
+                graph
+                    .get(n)
+                    .map(|&(index, _, _)| index)
+                    .ok_or_else(|| Error::unsolvable(format!("unknown node {n}")))
+            };
+
+            path[*index] = (node(l)?, node(r)?);
+        }
 
-    for (index, l, r) in graph.values() {
-        path[*index] = (graph.get(l).unwrap().0, graph.get(r).unwrap().0);
+        Ok(Network {
+            sequence,
+            path,
+            starting_z,
+            ending_z,
+            index_aaa,
+            index_zzz,
+        })
     }
 
-    let part1;
+    fn part1(network: &Self::Parsed) -> Result<usize> {
+        match (network.index_aaa, network.index_zzz) {
+            (Some(index_aaa), Some(index_zzz)) => compute(
+                &network.path,
+                index_aaa,
+                &[index_zzz],
+                &network.sequence,
+            ),
+            (Some(_), None) => Err(Error::unsolvable("no ZZZ node")),
+            _ => Ok(0),
+        }
+    }
 
-    if let Some(index_aaa) = index_aaa {
-        let index_zzz = index_zzz.unwrap();
+    fn part2(network: &Self::Parsed) -> Result<usize> {
+        if network.ending_z.is_empty() {
+            return Err(Error::unsolvable("no node ending with Z"));
+        }
 
-        part1 = compute(&path, index_aaa, &[index_zzz], &sequence);
-    } else {
-        part1 = 0;
+        network
+            .starting_z
+            .iter()
+            .map(|&s| compute(&network.path, s, &network.ending_z, &network.sequence))
+            .try_fold(1, |acc, n| Ok(lcm(acc, n?)))
     }
 
-    let part2 = starting_z
-        .iter()
-        .map(|&s| compute(&path, s, &ending_z, &sequence))
-        .fold(1, lcm);
-
-    (part1, part2)
+    fn validator() -> Validator {
+        Validator::new()
+            .paragraphs()
+            .lines(r"^([LR]+|\w{3} = \(\w{3}, \w{3}\))$")
+    }
 }
 
 #[test]
@@ -151,17 +213,36 @@ ZZZ = (ZZZ, ZZZ)";
 XXX = (XXX, XXX)";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST1).lines()), (2, 2));
-    assert_eq!(resolve(Cursor::new(TEST2).lines()), (6, 6));
-    assert_eq!(resolve(Cursor::new(TEST3).lines()), (0, 6));
+    assert_eq!(Day08::resolve(Cursor::new(TEST1).lines()).unwrap(), (2, 2));
+    assert_eq!(Day08::resolve(Cursor::new(TEST2).lines()).unwrap(), (6, 6));
+    assert_eq!(Day08::resolve(Cursor::new(TEST3).lines()).unwrap(), (0, 6));
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_unreachable() {
+    const TEST: &str = "L
+
+AAA = (AAA, AAA)
+ZZZ = (ZZZ, ZZZ)";
+    use std::io::Cursor;
+
+    let network = Day08::parse(Cursor::new(TEST).lines()).unwrap();
+
+    assert!(matches!(Day08::part1(&network), Err(Error::Unsolvable(_))));
+    assert!(matches!(Day08::part2(&network), Err(Error::Unsolvable(_))));
+}
+
+#[test]
+fn check_validator() {
+    // a missing comma between the nodes
+    assert!(matches!(
+        Day08::validator().check("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD EEE)"),
+        Err(Error::Invalid {
+            line: Some(4),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day08>(file!()) }
@@ -1,38 +1,71 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-fn resolve<T>(lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
-    lines.fold((0, 0), |(part1, part2), line| {
-        let line = line.unwrap();
+// the next and the previous values of the sequence
+fn extrapolate(numbers: &[i64]) -> (i64, i64) {
+    let mut numbers = numbers.to_vec();
+    let mut start_accumulators = vec![];
+    let mut next = 0;
 
-        let mut numbers = line
-            .split_whitespace()
-            .map(|digit| digit.parse::<i64>().unwrap())
+    loop {
+        next += numbers[numbers.len() - 1];
+        start_accumulators.push(numbers[0]);
+
+        numbers = numbers
+            .iter()
+            .zip(numbers.iter().skip(1))
+            .map(|(a, b)| b - a)
             .collect::<Vec<_>>();
-        let mut start_accumulators = vec![];
-        let mut p1 = 0;
-
-        loop {
-            p1 += numbers[numbers.len() - 1];
-            start_accumulators.push(numbers[0]);
-
-            numbers = numbers
-                .iter()
-                .zip(numbers.iter().skip(1))
-                .map(|(a, b)| b - a)
-                .collect::<Vec<_>>();
-
-            if numbers.iter().all(|&n| n == 0) {
-                break;
-            }
+
+        if numbers.iter().all(|&n| n == 0) {
+            break;
         }
+    }
+
+    let previous = start_accumulators.iter().rev().fold(0, |prev, s| s - prev);
+
+    (next, previous)
+}
+
+struct Day09;
 
-        let p2 = start_accumulators.iter().rev().fold(0, |prev, s| s - prev);
+impl Solution for Day09 {
+    type Parsed = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-        (part1 + p1, part2 + p2)
-    })
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                let numbers = line?
+                    .split_whitespace()
+                    .map(|digit| digit.parse::<i64>().at_line(index))
+                    .collect::<Result<Vec<_>>>()?;
+
+                if numbers.is_empty() {
+                    return Err(Error::parse(index, "empty sequence"));
+                }
+
+                Ok(numbers)
+            })
+            .collect()
+    }
+
+    fn part1(sequences: &Self::Parsed) -> Result<i64> {
+        Ok(sequences.iter().map(|s| extrapolate(s).0).sum())
+    }
+
+    fn part2(sequences: &Self::Parsed) -> Result<i64> {
+        Ok(sequences.iter().map(|s| extrapolate(s).1).sum())
+    }
+
+    fn validator() -> Validator {
+        Validator::new().lines(r"^-?\d+( -?\d+)*$")
+    }
 }
 
 #[test]
@@ -42,15 +75,20 @@ fn check() {
 10 13 16 21 30 45";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (114, 2));
+    assert_eq!(Day09::resolve(Cursor::new(TEST).lines()).unwrap(), (114, 2));
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // numbers separated by commas
+    assert!(matches!(
+        Day09::validator().check("0 3 6 9 12 15\n1,3,6,10,15,21"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day09>(file!()) }
@@ -1,135 +1,169 @@
+use advent_2023::{Direction, Error, Grid, Point, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-fn get_next_direction(direction: (i32, i32), c: u8) -> (i32, i32) {
-    match (direction, c) {
-        // DOWN
-        ((0, 1), b'|') => (0, 1),
-        ((0, 1), b'J') => (-1, 0),
-        ((0, 1), b'L') => (1, 0),
-        // UP
-        ((0, -1), b'|') => (0, -1),
-        ((0, -1), b'7') => (-1, 0),
-        ((0, -1), b'F') => (1, 0),
-        // RIGHT
-        ((1, 0), b'-') => (1, 0),
-        ((1, 0), b'J') => (0, -1),
-        ((1, 0), b'7') => (0, 1),
-        // LEFT
-        ((-1, 0), b'-') => (-1, 0),
-        ((-1, 0), b'L') => (0, -1),
-        ((-1, 0), b'F') => (0, 1),
-        _ => panic!(),
-    }
+fn get_next_direction(direction: Direction, c: u8) -> Option<Direction> {
+    use Direction::*;
+
+    Some(match (direction, c) {
+        (Down, b'|') => Down,
+        (Down, b'J') => Left,
+        (Down, b'L') => Right,
+        (Up, b'|') => Up,
+        (Up, b'7') => Left,
+        (Up, b'F') => Right,
+        (Right, b'-') => Right,
+        (Right, b'J') => Up,
+        (Right, b'7') => Down,
+        (Left, b'-') => Left,
+        (Left, b'L') => Up,
+        (Left, b'F') => Down,
+        _ => return None,
+    })
 }
 
-fn get_start_pipe(start_direction: (i32, i32), end_direction: (i32, i32)) -> u8 {
-    match (start_direction, end_direction) {
-        ((0, -1), (-1, 0)) => b'L',
-        ((0, -1), (0, -1)) => b'|',
-        ((0, -1), (1, 0)) => b'J',
-
-        ((1, 0), (0, -1)) => b'F',
-        ((1, 0), (1, 0)) => b'-',
+fn get_start_pipe(start_direction: Direction, end_direction: Direction) -> Option<u8> {
+    use Direction::*;
 
-        ((0, 1), (1, 0)) => b'7',
-        _ => panic!(),
-    }
-}
+    Some(match (start_direction, end_direction) {
+        (Up, Left) => b'L',
+        (Up, Up) => b'|',
+        (Up, Right) => b'J',
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut grid = vec![];
-    let mut start = (0, 0);
+        (Right, Up) => b'F',
+        (Right, Right) => b'-',
 
-    for line in lines {
-        let line = line.unwrap();
-        let bytes = line.as_bytes();
-
-        let index = bytes.iter().position(|&c| c == b'S');
-        if let Some(index) = index {
-            start = (index, grid.len());
-        }
+        (Down, Right) => b'7',
+        _ => return None,
+    })
+}
 
-        grid.push(bytes.to_owned());
-    }
+// the pipes of the loop only, with S replaced by its pipe, and the length
+// of the loop
+struct Loop {
+    pipes: Grid<u8>,
+    length: usize,
+}
 
-    let start_direction;
+fn find_loop(grid: &Grid<u8>, start: Point) -> Result<Loop> {
+    let connects = |d: Direction, pipes: &[u8; 3]| {
+        grid.get(start + d.vector())
+            .is_some_and(|c| pipes.contains(c))
+    };
 
     // find next position to start
-    if start.1 > 0 && [b'7', b'|', b'F'].contains(&grid[start.1 - 1][start.0]) {
-        start_direction = (0, -1);
-    } else if start.0 < grid[0].len() - 1
-        && [b'7', b'-', b'J'].contains(&grid[start.1][start.0 + 1])
-    {
-        start_direction = (1, 0);
-    } else if start.1 < grid.len() - 1 && [b'J', b'|', b'L'].contains(&grid[start.1 + 1][start.0]) {
-        start_direction = (0, 1);
-    } else if start.0 > 0 && [b'F', b'-', b'L'].contains(&grid[start.1][start.0 - 1]) {
-        start_direction = (-1, 0);
-    } else {
-        panic!();
-    }
+    let start_direction = [
+        (Direction::Up, b"7|F"),
+        (Direction::Right, b"7-J"),
+        (Direction::Down, b"J|L"),
+        (Direction::Left, b"F-L"),
+    ]
+    .into_iter()
+    .find(|(d, pipes)| connects(*d, pipes))
+    .map(|(d, _)| d)
+    .ok_or_else(|| Error::unsolvable("S is not connected to any pipe"))?;
 
     let mut direction = start_direction;
-    let mut current = (start.0 as i32 + direction.0, start.1 as i32 + direction.1);
-    let mut grid2 = vec![vec![b'.'; grid[0].len()]; grid.len()];
-    let mut part1 = 1;
+    let mut current = start + direction.vector();
+    let mut grid2 = Grid::new(grid.width(), grid.height(), b'.');
+    let mut length = 1;
 
     loop {
-        let pipe = grid[current.1 as usize][current.0 as usize];
+        let pipe = grid
+            .get(current)
+            .copied()
+            .ok_or_else(|| Error::unsolvable("the loop leaves the grid"))?;
 
         if pipe == b'S' {
             break;
         }
 
-        grid2[current.1 as usize][current.0 as usize] = pipe;
+        grid2[current] = pipe;
 
-        direction = get_next_direction(direction, pipe);
+        direction = get_next_direction(direction, pipe)
+            .ok_or_else(|| Error::unsolvable(format!("the loop is broken at {current:?}")))?;
 
-        current = (current.0 + direction.0, current.1 + direction.1);
+        current += direction.vector();
 
-        part1 += 1;
+        length += 1;
+
+        // the pipes cannot send the walk round a loop without S, this is
+        // only a safety net
+        if length > grid.width() * grid.height() {
+            return Err(Error::unsolvable("the loop never comes back to S"));
+        }
     }
 
     // replace the S by the real pipe
-    grid2[start.1][start.0] = get_start_pipe(start_direction, direction);
+    grid2[start] = get_start_pipe(start_direction, direction)
+        .ok_or_else(|| Error::unsolvable("cannot guess the pipe under S"))?;
 
-    let mut part2 = 0;
+    Ok(Loop {
+        pipes: grid2,
+        length,
+    })
+}
 
-    for y in 0..grid2.len() {
-        let mut is_inside = false;
-        let mut entered_pipe = b' ';
+struct Day10;
 
-        for x in 0..grid2[0].len() {
-            let pipe = grid2[y][x];
+impl Solution for Day10 {
+    type Parsed = Loop;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-            match pipe {
-                b'.' => {
-                    if is_inside {
-                        part2 += 1
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let grid = Grid::parse(lines, Some)?;
+        let start = grid
+            .position(|&c| c == b'S')
+            .ok_or_else(|| Error::unsolvable("no S in the grid"))?;
+
+        find_loop(&grid, start)
+    }
+
+    fn part1(maze_loop: &Self::Parsed) -> Result<usize> {
+        Ok(maze_loop.length / 2)
+    }
+
+    fn part2(maze_loop: &Self::Parsed) -> Result<usize> {
+        let mut part2 = 0;
+
+        for row in maze_loop.pipes.rows() {
+            let mut is_inside = false;
+            let mut entered_pipe = b' ';
+
+            for &pipe in row.iter() {
+                match pipe {
+                    b'.' => {
+                        if is_inside {
+                            part2 += 1
+                        }
                     }
-                }
-                b'|' => is_inside = !is_inside,
-                b'L' | b'F' => entered_pipe = pipe,
-                b'J' => {
-                    if entered_pipe == b'F' {
-                        is_inside = !is_inside
+                    b'|' => is_inside = !is_inside,
+                    b'L' | b'F' => entered_pipe = pipe,
+                    b'J' => {
+                        if entered_pipe == b'F' {
+                            is_inside = !is_inside
+                        }
                     }
-                }
-                b'7' => {
-                    if entered_pipe == b'L' {
-                        is_inside = !is_inside
+                    b'7' => {
+                        if entered_pipe == b'L' {
+                            is_inside = !is_inside
+                        }
                     }
+                    b'-' => {}
+                    _ => unreachable!(),
                 }
-                b'-' => {}
-                _ => panic!(),
             }
         }
+
+        Ok(part2)
     }
 
-    (part1 / 2, part2)
+    fn validator() -> Validator {
+        Validator::new().grid().alphabet("|-LJ7F.S").exactly('S', 1)
+    }
 }
 
 #[test]
@@ -175,19 +209,27 @@ L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST1).lines()), (4, 1));
-    assert_eq!(resolve(Cursor::new(TEST2).lines()), (8, 1));
-    assert_eq!(resolve(Cursor::new(TEST3).lines()), (23, 4));
-    assert_eq!(resolve(Cursor::new(TEST4).lines()), (70, 8));
-    assert_eq!(resolve(Cursor::new(TEST5).lines()), (80, 10));
+    assert_eq!(Day10::resolve(Cursor::new(TEST1).lines()).unwrap(), (4, 1));
+    assert_eq!(Day10::resolve(Cursor::new(TEST2).lines()).unwrap(), (8, 1));
+    assert_eq!(Day10::resolve(Cursor::new(TEST3).lines()).unwrap(), (23, 4));
+    assert_eq!(Day10::resolve(Cursor::new(TEST4).lines()).unwrap(), (70, 8));
+    assert_eq!(
+        Day10::resolve(Cursor::new(TEST5).lines()).unwrap(),
+        (80, 10)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    let position = |input| match Day10::validator().check(input) {
+        Err(Error::Invalid { line, column, .. }) => (line, column),
+        _ => panic!("{input:?} is valid"),
+    };
+
+    // the tiles marked inside by the puzzle text are not in the inputs
+    assert_eq!(position("..F7.\n.FJ|.\nSJIL7\n|F--J"), (Some(3), Some(3)));
+    // a second start
+    assert_eq!(position(".S-7.\n.|.|.\n.L-S."), (Some(3), Some(4)));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day10>(file!()) }
@@ -1,3 +1,4 @@
+use advent_2023::{Error, Grid, Point, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
 fn compute_free(v: &[bool]) -> Vec<usize> {
@@ -20,48 +21,85 @@ fn distance(a: usize, b: usize, spaces: &[usize], factor: usize) -> usize {
     max - min + factor * (spaces[max] - spaces[min])
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut points = vec![];
-    let mut rows = vec![];
-    let mut columns = vec![];
-    let factor = if cfg!(test) { 10 - 1 } else { 1_000_000 - 1 };
-
-    for line in lines {
-        let line = line.unwrap();
-        let mut row_not_empty = false;
-
-        for (index, c) in line.chars().enumerate() {
-            if index >= columns.len() {
-                columns.resize(index + 1, false);
-            }
-            if c == '#' {
-                points.push((index, rows.len()));
-                columns[index] = true;
-                row_not_empty = true;
+struct Image {
+    points: Vec<Point>,
+    // the count of empty rows and columns up to each index
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+}
+
+impl Image {
+    fn distances(&self, factor: usize) -> usize {
+        let points = &self.points;
+        let mut sum = 0;
+
+        for i in 0..points.len() - 1 {
+            for j in i + 1..points.len() {
+                let a = points[i];
+                let b = points[j];
+
+                sum += distance(a.x as usize, b.x as usize, &self.columns, factor)
+                    + distance(a.y as usize, b.y as usize, &self.rows, factor);
             }
         }
 
-        rows.push(row_not_empty);
+        sum
+    }
+}
+
+struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Image;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let image = Grid::parse(lines, |c| match c {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        })?;
+        let points = image
+            .iter()
+            .filter_map(|(p, &galaxy)| galaxy.then_some(p))
+            .collect::<Vec<_>>();
+        let rows = image
+            .rows()
+            .map(|row| row.contains(&true))
+            .collect::<Vec<_>>();
+        let columns = image
+            .columns()
+            .map(|mut column| column.any(|&galaxy| galaxy))
+            .collect::<Vec<_>>();
+
+        if points.len() < 2 {
+            return Err(Error::unsolvable("less than 2 galaxies"));
+        }
+
+        Ok(Image {
+            points,
+            rows: compute_free(&rows),
+            columns: compute_free(&columns),
+        })
     }
 
-    let rows = compute_free(&rows);
-    let columns = compute_free(&columns);
-    let (mut part1, mut part2) = (0, 0);
+    fn part1(image: &Self::Parsed) -> Result<usize> {
+        Ok(image.distances(1))
+    }
 
-    for i in 0..points.len() - 1 {
-        for j in i + 1..points.len() {
-            let a = points[i];
-            let b = points[j];
+    fn part2(image: &Self::Parsed) -> Result<usize> {
+        let factor = if cfg!(test) { 10 - 1 } else { 1_000_000 - 1 };
 
-            part1 += distance(a.0, b.0, &columns, 1) + distance(a.1, b.1, &rows, 1);
-            part2 += distance(a.0, b.0, &columns, factor) + distance(a.1, b.1, &rows, factor);
-        }
+        Ok(image.distances(factor))
     }
 
-    (part1, part2)
+    fn validator() -> Validator {
+        Validator::new().grid().alphabet(".#")
+    }
 }
 
 #[test]
@@ -78,15 +116,23 @@ fn check() {
 #...#.....";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (374, 1030));
+    assert_eq!(
+        Day11::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (374, 1030)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a galaxy drawn with another character
+    assert!(matches!(
+        Day11::validator().check("...#......\n.......*.."),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(8),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day11>(file!()) }
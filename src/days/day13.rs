@@ -1,85 +1,101 @@
-use advent_2023::Paragrapher;
+use advent_2023::{Error, Grid, Paragrapher, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
+// the first vertical line of reflection with no difference and the one with
+// exactly one, 0 when there is none
+fn find_vertical(grid: &Grid<u8>) -> (usize, usize) {
+    let w = grid.width();
+    let mut verticals = vec![0; w];
+
+    verticals[0] = 2;
+
+    for row in grid.rows() {
+        for (index, v) in verticals.iter_mut().enumerate() {
+            if *v > 1 {
+                continue;
+            }
+
+            *v += (0..index)
+                .rev()
+                .zip(index..w)
+                .filter(|&(x1, x2)| row[x1] != row[x2])
+                .count();
+        }
+    }
+
+    (
+        verticals.iter().position(|&x| x == 0).unwrap_or(0),
+        verticals.iter().position(|&x| x == 1).unwrap_or(0),
+    )
+}
+
 #[derive(Debug)]
 struct Pattern {
-    grid: Vec<Vec<u8>>,
-    w: usize,
-    h: usize,
+    grid: Grid<u8>,
+    transposed: Grid<u8>,
 }
 
 impl Pattern {
-    fn new(grid: Vec<Vec<u8>>) -> Self {
-        let w = grid[0].len();
-        let h = grid.len();
+    // index is the line index of the first row of the pattern
+    fn new(rows: Vec<Vec<u8>>, index: usize) -> Result<Self> {
+        let grid = Grid::from_rows(rows)
+            .ok_or_else(|| Error::parse(index, "pattern is empty or not rectangular"))?;
+        let transposed = grid.transpose();
 
-        Pattern { grid, w, h }
+        Ok(Pattern { grid, transposed })
     }
 
     fn find_vertical(&self) -> (usize, usize) {
-        let mut verticals = vec![0; self.w];
+        find_vertical(&self.grid)
+    }
 
-        verticals[0] = 2;
+    fn find_horyzontal(&self) -> (usize, usize) {
+        find_vertical(&self.transposed)
+    }
+}
 
-        for y in 0..self.h {
-            for (index, v) in verticals.iter_mut().enumerate() {
-                if *v > 1 {
-                    continue;
-                }
+struct Day13;
 
-                *v += (0..index)
-                    .rev()
-                    .zip(index..self.w)
-                    .filter(|&(x1, x2)| self.grid[y][x1] != self.grid[y][x2])
-                    .count();
-            }
-        }
+impl Solution for Day13 {
+    type Parsed = Vec<Pattern>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        (
-            verticals.iter().position(|&x| x == 0).unwrap_or(0),
-            verticals.iter().position(|&x| x == 1).unwrap_or(0),
-        )
-    }
+    fn parse<T>(mut lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let mut index = 0;
 
-    fn find_horyzontal(&self) -> (usize, usize) {
-        let mut horyzontals = vec![0; self.h];
+        lines
+            .split_paragraph(|s| s.as_bytes().to_owned())
+            .map(|grid| {
+                let pattern = Pattern::new(grid?, index)?;
 
-        horyzontals[0] = 2;
+                index += pattern.grid.height() + 1;
 
-        for x in 0..self.w {
-            for (index, v) in horyzontals.iter_mut().enumerate() {
-                if *v > 1 {
-                    continue;
-                }
+                Ok(pattern)
+            })
+            .collect()
+    }
 
-                *v += (0..index)
-                    .rev()
-                    .zip(index..self.h)
-                    .filter(|&(y1, y2)| self.grid[y1][x] != self.grid[y2][x])
-                    .count();
-            }
-        }
+    fn part1(patterns: &Self::Parsed) -> Result<usize> {
+        Ok(patterns
+            .iter()
+            .map(|p| 100 * p.find_horyzontal().0 + p.find_vertical().0)
+            .sum())
+    }
 
-        (
-            horyzontals.iter().position(|&y| y == 0).unwrap_or(0),
-            horyzontals.iter().position(|&y| y == 1).unwrap_or(0),
-        )
+    fn part2(patterns: &Self::Parsed) -> Result<usize> {
+        Ok(patterns
+            .iter()
+            .map(|p| 100 * p.find_horyzontal().1 + p.find_vertical().1)
+            .sum())
     }
-}
 
-fn resolve<T>(mut lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    lines
-        .split_paragraph(|s| s.as_bytes().to_owned())
-        .map(Pattern::new)
-        .fold((0, 0), |(p1, p2), pattern| {
-            let (v1, v2) = pattern.find_vertical();
-            let (h1, h2) = pattern.find_horyzontal();
-
-            (p1 + 100 * h1 + v1, p2 + 100 * h2 + v2)
-        })
+    fn validator() -> Validator {
+        Validator::new().paragraphs().grid().alphabet(".#")
+    }
 }
 
 #[test]
@@ -101,15 +117,23 @@ fn check() {
 #....#..#";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (405, 400));
+    assert_eq!(
+        Day13::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (405, 400)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // the second pattern is not rectangular
+    assert!(matches!(
+        Day13::validator().check("#.##.\n..#.#\n\n#...#\n#..#"),
+        Err(Error::Invalid {
+            line: Some(5),
+            column: Some(5),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day13>(file!()) }
@@ -1,18 +1,18 @@
+use advent_2023::{Cycle, Grid, Point, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-fn go_north(grid: &mut [Vec<u8>]) {
-    let w = grid[0].len();
-    let h = grid.len();
+fn go_north(grid: &mut Grid<u8>) {
+    let (w, h) = (grid.width() as i64, grid.height() as i64);
 
     for x in 0..w {
         let mut count = 0;
 
         for y in (0..h).rev() {
-            let c = &mut grid[y][x];
+            let c = &mut grid[Point::new(x, y)];
 
             if *c == b'#' {
                 for j in 0..count {
-                    grid[y + j + 1][x] = b'O';
+                    grid[Point::new(x, y + j + 1)] = b'O';
                 }
                 count = 0;
             } else if *c == b'O' {
@@ -21,25 +21,24 @@ fn go_north(grid: &mut [Vec<u8>]) {
             }
         }
 
-        for row in grid.iter_mut().take(count) {
-            row[x] = b'O';
+        for y in 0..count {
+            grid[Point::new(x, y)] = b'O';
         }
     }
 }
 
-fn go_south(grid: &mut [Vec<u8>]) {
-    let w = grid[0].len();
-    let h = grid.len();
+fn go_south(grid: &mut Grid<u8>) {
+    let (w, h) = (grid.width() as i64, grid.height() as i64);
 
     for x in 0..w {
         let mut count = 0;
 
         for y in 0..h {
-            let c = &mut grid[y][x];
+            let c = &mut grid[Point::new(x, y)];
 
             if *c == b'#' {
                 for j in 0..count {
-                    grid[y - j - 1][x] = b'O';
+                    grid[Point::new(x, y - j - 1)] = b'O';
                 }
                 count = 0;
             } else if *c == b'O' {
@@ -48,16 +47,17 @@ fn go_south(grid: &mut [Vec<u8>]) {
             }
         }
 
-        for j in 0..count {
-            grid[h - j - 1][x] = b'O';
+        for y in h - count..h {
+            grid[Point::new(x, y)] = b'O';
         }
     }
 }
 
-fn go_west(grid: &mut [Vec<u8>]) {
-    let w = grid[0].len();
+fn go_west(grid: &mut Grid<u8>) {
+    let (w, h) = (grid.width(), grid.height());
 
-    for row in grid.iter_mut() {
+    for y in 0..h {
+        let row = grid.row_mut(y);
         let mut count = 0;
 
         for x in (0..w).rev() {
@@ -80,10 +80,11 @@ fn go_west(grid: &mut [Vec<u8>]) {
     }
 }
 
-fn go_east(grid: &mut [Vec<u8>]) {
-    let w = grid[0].len();
+fn go_east(grid: &mut Grid<u8>) {
+    let (w, h) = (grid.width(), grid.height());
 
-    for row in grid.iter_mut() {
+    for y in 0..h {
+        let row = grid.row_mut(y);
         let mut count = 0;
 
         for x in 0..w {
@@ -106,96 +107,52 @@ fn go_east(grid: &mut [Vec<u8>]) {
     }
 }
 
-fn cycle(grid: &mut [Vec<u8>]) {
+fn cycle(grid: &mut Grid<u8>) {
     go_north(grid);
     go_west(grid);
     go_south(grid);
     go_east(grid);
 }
 
-fn equal(grid1: &[Vec<u8>], grid2: &[Vec<u8>]) -> bool {
-    let w = grid1[0].len();
-    let h = grid1.len();
-
-    for x in 0..w {
-        for y in 0..h {
-            if grid1[y][x] != grid2[y][x] {
-                return false;
-            }
-        }
-    }
-    true
-}
-
-fn load(grid: &[Vec<u8>]) -> usize {
-    grid.iter().rev().enumerate().fold(0, |load, (index, row)| {
+fn load(grid: &Grid<u8>) -> usize {
+    grid.rows().rev().enumerate().fold(0, |load, (index, row)| {
         load + (index + 1) * row.iter().filter(|&c| *c == b'O').count()
     })
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let initial = lines
-        .map(|l| l.unwrap().as_bytes().to_owned())
-        .collect::<Vec<_>>();
-    let mut tortoise = initial.clone();
-
-    go_north(&mut tortoise);
+struct Day14;
 
-    let part1 = load(&tortoise);
+impl Solution for Day14 {
+    type Parsed = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    go_west(&mut tortoise);
-    go_south(&mut tortoise);
-    go_east(&mut tortoise);
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let initial = Grid::parse(lines, Some)?;
 
-    // https://en.wikipedia.org/wiki/Cycle_detection
-
-    let mut hare = tortoise.clone();
-    cycle(&mut hare);
-
-    while !equal(&tortoise, &hare) {
-        cycle(&mut tortoise);
-        cycle(&mut hare);
-        cycle(&mut hare);
-    }
-
-    // find start of the loop
-    tortoise = initial.clone();
-    let mut mu = 0;
-
-    while !equal(&tortoise, &hare) {
-        cycle(&mut tortoise);
-        cycle(&mut hare);
-        mu += 1;
+        Ok(initial)
     }
 
-    // find the size of the loop
-    let mut lam = 1;
+    fn part1(initial: &Self::Parsed) -> Result<usize> {
+        let mut platform = initial.clone();
 
-    hare = tortoise.clone();
-    cycle(&mut hare);
+        go_north(&mut platform);
 
-    while !equal(&tortoise, &hare) {
-        cycle(&mut hare);
-        lam += 1;
+        Ok(load(&platform))
     }
 
-    let rest = (1_000_000_000 - mu) % lam;
+    fn part2(initial: &Self::Parsed) -> Result<usize> {
+        let spins = Cycle::brent(initial, cycle)?;
 
-    // tortoise = initial.clone();
-
-    // for _ in 0..(mu + rest) {
-    //     cycle(&mut tortoise);
-    // }
-
-    // tortoise is already at mu cycles
-    for _ in 0..rest {
-        cycle(&mut tortoise);
+        Ok(load(&spins.nth(initial, cycle, 1_000_000_000)))
     }
 
-    (part1, load(&tortoise))
+    fn validator() -> Validator {
+        Validator::new().grid().alphabet(".#O")
+    }
 }
 
 #[test]
@@ -212,15 +169,25 @@ O.#..O.#.#
 #OO..#....";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (136, 64));
+    assert_eq!(
+        Day14::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (136, 64)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    use advent_2023::Error;
+
+    // a zero instead of a rounded rock
+    assert!(matches!(
+        Day14::validator().check("O....#....\nO.0O#....#"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(3),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day14>(file!()) }
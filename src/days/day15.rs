@@ -1,3 +1,4 @@
+use advent_2023::{AtLine, Error, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
 fn hash(s: &[u8]) -> usize {
@@ -16,52 +17,90 @@ fn check_hash() {
     assert_eq!(hash("HASH".as_bytes()), 52);
 }
 
-fn resolve<T>(mut lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let line = lines.next().unwrap().unwrap();
-    let mut part1 = 0;
-    let mut boxes: Vec<Vec<(&[u8], u8)>> = vec![vec![]; 256];
-
-    for s in line.split(',') {
-        let s = s.as_bytes();
-
-        part1 += hash(s);
-
-        let equal = s.iter().position(|&x| x == b'=');
-
-        if let Some(equal) = equal {
-            let h = hash(&s[0..equal]);
-            let l = boxes[h]
-                .iter_mut()
-                .find(|(label, _)| *label == &s[0..equal]);
-            let v = s[equal + 1] - b'0';
-            if let Some(l) = l {
-                l.1 = v;
-            } else {
-                boxes[h].push((&s[0..equal], v));
-            }
-        } else {
-            let h = hash(&s[0..s.len() - 1]);
-            let l = boxes[h]
-                .iter()
-                .position(|(label, _)| *label == &s[0..s.len() - 1]);
-            if let Some(l) = l {
-                boxes[h].remove(l);
+enum Operation {
+    Remove,
+    Set(u8),
+}
+
+struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
+struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Vec<Step>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<T>(mut lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let line = lines.next().at_line(0)??;
+
+        line.split(',')
+            .map(|s| {
+                let (label, operation) = if let Some((label, v)) = s.split_once('=') {
+                    let v = match v.as_bytes() {
+                        [v @ b'1'..=b'9'] => v - b'0',
+                        _ => return Err(Error::parse(0, "invalid focal length")),
+                    };
+
+                    (label, Operation::Set(v))
+                } else if let Some(label) = s.strip_suffix('-') {
+                    (label, Operation::Remove)
+                } else {
+                    return Err(Error::parse(0, "expected `=` or `-` operation"));
+                };
+
+                Ok(Step {
+                    text: s.to_string(),
+                    label: label.to_string(),
+                    operation,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(steps: &Self::Parsed) -> Result<usize> {
+        Ok(steps.iter().map(|s| hash(s.text.as_bytes())).sum())
+    }
+
+    fn part2(steps: &Self::Parsed) -> Result<usize> {
+        let mut boxes: Vec<Vec<(&str, u8)>> = vec![vec![]; 256];
+
+        for step in steps {
+            let label = step.label.as_str();
+            let b = &mut boxes[hash(label.as_bytes())];
+            let l = b.iter().position(|&(l, _)| l == label);
+
+            match (&step.operation, l) {
+                (Operation::Set(v), Some(l)) => b[l].1 = *v,
+                (Operation::Set(v), None) => b.push((label, *v)),
+                (Operation::Remove, Some(l)) => {
+                    b.remove(l);
+                }
+                (Operation::Remove, None) => {}
             }
         }
-    }
 
-    let mut part2 = 0;
+        let mut part2 = 0;
 
-    for (i, b) in boxes.iter().enumerate() {
-        for (j, l) in b.iter().enumerate() {
-            part2 += (i + 1) * (j + 1) * l.1 as usize;
+        for (i, b) in boxes.iter().enumerate() {
+            for (j, l) in b.iter().enumerate() {
+                part2 += (i + 1) * (j + 1) * l.1 as usize;
+            }
         }
+
+        Ok(part2)
     }
 
-    (part1, part2)
+    fn validator() -> Validator {
+        Validator::new().lines(r"^[a-z]+(=[1-9]|-)(,[a-z]+(=[1-9]|-))*$")
+    }
 }
 
 #[test]
@@ -69,15 +108,23 @@ fn check() {
     const TEST: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (1320, 145));
+    assert_eq!(
+        Day15::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (1320, 145)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a focal length of 0
+    assert!(matches!(
+        Day15::validator().check("rn=1,cm-,qp=0"),
+        Err(Error::Invalid {
+            line: Some(1),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day15>(file!()) }
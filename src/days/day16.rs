@@ -1,13 +1,14 @@
+use advent_2023::{check_deadline, Direction, Grid, Point, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
 #[derive(Debug)]
 struct Beam {
-    position: (i32, i32),
-    direction: (i32, i32),
+    position: Point,
+    direction: Direction,
 }
 
 impl Beam {
-    fn new(position: (i32, i32), direction: (i32, i32)) -> Self {
+    fn new(position: Point, direction: Direction) -> Self {
         Beam {
             position,
             direction,
@@ -15,32 +16,15 @@ impl Beam {
     }
 
     fn next(&self) -> Self {
-        let position = (
-            self.position.0 + self.direction.0,
-            self.position.1 + self.direction.1,
-        );
-        Beam {
-            position,
-            direction: self.direction,
-        }
+        Beam::new(self.position + self.direction.vector(), self.direction)
     }
 
     fn left(&self) -> Self {
-        let direction = (self.direction.1, -self.direction.0);
-
-        Beam {
-            position: self.position,
-            direction,
-        }
+        Beam::new(self.position, self.direction.turn_left())
     }
 
     fn right(&self) -> Self {
-        let direction = (-self.direction.1, self.direction.0);
-
-        Beam {
-            position: self.position,
-            direction,
-        }
+        Beam::new(self.position, self.direction.turn_right())
     }
 
     fn split(&self) -> (Self, Self) {
@@ -50,130 +34,146 @@ impl Beam {
 
 #[test]
 fn check_rotation() {
-    let mut b = Beam::new((0, 0), (0, -1));
+    use Direction::*;
+
+    let mut b = Beam::new(Point::ORIGIN, Up);
 
     b = b.left();
-    assert_eq!(b.direction, (-1, 0));
+    assert_eq!(b.direction, Left);
 
     b = b.left();
-    assert_eq!(b.direction, (0, 1));
+    assert_eq!(b.direction, Down);
 
     b = b.left();
-    assert_eq!(b.direction, (1, 0));
+    assert_eq!(b.direction, Right);
 
     b = b.left();
-    assert_eq!(b.direction, (0, -1));
+    assert_eq!(b.direction, Up);
 
     b = b.right();
-    assert_eq!(b.direction, (1, 0));
+    assert_eq!(b.direction, Right);
 
     b = b.right();
-    assert_eq!(b.direction, (0, 1));
+    assert_eq!(b.direction, Down);
 
     b = b.right();
-    assert_eq!(b.direction, (-1, 0));
+    assert_eq!(b.direction, Left);
 
     b = b.right();
-    assert_eq!(b.direction, (0, -1));
+    assert_eq!(b.direction, Up);
 }
 
-fn energize(contraption: &[Vec<u8>], starting: Beam) -> usize {
+// the directions the beams went through every tile, a beam already seen is
+// on a loop of mirrors and is dropped
+fn energize(contraption: &Grid<u8>, starting: Beam) -> usize {
     let mut stack = vec![];
-    let w = contraption[0].len();
-    let h = contraption.len();
-    let mut energized = vec![vec![false; w]; h];
+    let mut energized = Grid::new(contraption.width(), contraption.height(), 0u8);
 
     stack.push(starting);
 
     while let Some(beam) = stack.pop() {
         let beam = beam.next();
 
-        if beam.position.0 < 0 || beam.position.1 < 0 {
+        let Some(&tile) = contraption.get(beam.position) else {
             // we are outside the contraption
             continue;
-        }
+        };
+        let energized = &mut energized[beam.position];
+        let direction = 1 << beam.direction.index();
+        let vertical = beam.direction.is_vertical();
 
-        let (x, y) = (beam.position.0 as usize, beam.position.1 as usize);
-
-        if x >= w || y >= h {
-            // we are outside the contraption
+        if *energized & direction != 0 {
             continue;
         }
 
-        match (contraption[y][x], beam.direction) {
+        match tile {
             // do forward
-            (b'.', _) => stack.push(beam),
-            (b'|', (0, _)) => stack.push(beam),
-            (b'-', (_, 0)) => stack.push(beam),
+            b'.' => stack.push(beam),
+            b'|' if vertical => stack.push(beam),
+            b'-' if !vertical => stack.push(beam),
             // turn
-            (b'/', (0, _)) => stack.push(beam.right()),
-            (b'/', (_, 0)) => stack.push(beam.left()),
-            (b'\\', (0, _)) => stack.push(beam.left()),
-            (b'\\', (_, 0)) => stack.push(beam.right()),
+            b'/' if vertical => stack.push(beam.right()),
+            b'/' => stack.push(beam.left()),
+            b'\\' if vertical => stack.push(beam.left()),
+            b'\\' => stack.push(beam.right()),
             // divide
-            (b'|', (_, 0)) => {
-                if !energized[y][x] {
-                    let (l, r) = beam.split();
-                    stack.push(l);
-                    stack.push(r);
-                }
-            }
-            (b'-', (0, _)) => {
-                if !energized[y][x] {
+            b'|' | b'-' => {
+                if *energized == 0 {
                     let (l, r) = beam.split();
                     stack.push(l);
                     stack.push(r);
                 }
             }
-            _ => panic!(),
+            _ => unreachable!(),
         }
 
-        energized[y][x] = true;
+        *energized |= direction;
     }
 
-    energized.into_iter().flatten().filter(|&e| e).count()
+    energized.rows().flatten().filter(|&&e| e != 0).count()
 }
 
-fn part2(contraption: &[Vec<u8>]) -> usize {
-    let w = contraption[0].len();
-    let h = contraption.len();
+fn part2(contraption: &Grid<u8>) -> Result<usize> {
+    let (w, h) = (contraption.width() as i64, contraption.height() as i64);
     let mut max_energy = 0;
 
     for y in 0..h {
-        let b_right = Beam::new((-1, y as i32), (1, 0));
+        check_deadline()?;
+
+        let b_right = Beam::new(Point::new(-1, y), Direction::Right);
 
         max_energy = max_energy.max(energize(contraption, b_right));
 
-        let b_left = Beam::new((w as i32, y as i32), (-1, 0));
+        let b_left = Beam::new(Point::new(w, y), Direction::Left);
 
         max_energy = max_energy.max(energize(contraption, b_left));
     }
 
     for x in 0..w {
-        let b_down = Beam::new((x as i32, 0 - 1), (0, 1));
+        check_deadline()?;
+
+        let b_down = Beam::new(Point::new(x, -1), Direction::Down);
 
         max_energy = max_energy.max(energize(contraption, b_down));
 
-        let b_up = Beam::new((x as i32, h as i32), (0, -1));
+        let b_up = Beam::new(Point::new(x, h), Direction::Up);
 
         max_energy = max_energy.max(energize(contraption, b_up));
     }
 
-    max_energy
+    Ok(max_energy)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let contraption = lines
-        .map(|line| line.unwrap().as_bytes().to_vec())
-        .collect::<Vec<_>>();
-
-    (
-        energize(&contraption, Beam::new((-1, 0), (1, 0))),
-        part2(&contraption),
-    )
+struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let contraption = Grid::parse(lines, |c| b"./\\|-".contains(&c).then_some(c))?;
+
+        Ok(contraption)
+    }
+
+    fn part1(contraption: &Self::Parsed) -> Result<usize> {
+        Ok(energize(
+            contraption,
+            Beam::new(Point::new(-1, 0), Direction::Right),
+        ))
+    }
+
+    fn part2(contraption: &Self::Parsed) -> Result<usize> {
+        part2(contraption)
+    }
+
+    fn validator() -> Validator {
+        Validator::new().grid().alphabet(r"./\|-")
+    }
 }
 
 #[test]
@@ -190,15 +190,34 @@ fn check() {
 ..//.|....";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (46, 51));
+    assert_eq!(Day16::resolve(Cursor::new(TEST).lines()).unwrap(), (46, 51));
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_mirror_loop() {
+    const TEST: &str = r"\./\
+\.|.
+..\/";
+    use std::io::Cursor;
+
+    let contraption = Day16::parse(Cursor::new(TEST).lines()).unwrap();
+
+    assert_eq!(Day16::part1(&contraption).unwrap(), 9);
+}
+
+#[test]
+fn check_validator() {
+    use advent_2023::Error;
+
+    // a trailing space on a row
+    assert!(matches!(
+        Day16::validator().check(".|...\\....\n|.-.\\.... "),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(10),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day16>(file!()) }
@@ -1,117 +1,84 @@
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use advent_2023::{Direction, Error, Grid, Point, Result, Search, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-#[derive(Debug, Eq, PartialEq)]
-struct Crucible {
-    position: (i32, i32),
-    direction: (i32, i32),
-    heat: u32,
-}
-
-impl Ord for Crucible {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.heat.cmp(&self.heat)
-    }
-}
-
-impl PartialOrd for Crucible {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Crucible {
-    fn new(position: (i32, i32), direction: (i32, i32), heat: u32) -> Self {
-        Crucible {
-            position,
-            direction,
-            heat,
+// where the crucible is and the way it came in, none at the start as it can
+// go anywhere
+type Crucible = (Point, Option<Direction>);
+
+// the crucible turns then goes straight for least to most blocks
+fn moves(
+    blocks: &Grid<u32>,
+    (position, direction): Crucible,
+    least: usize,
+    most: usize,
+) -> Vec<(Crucible, u32)> {
+    let mut moves = vec![];
+
+    for d in Direction::ALL {
+        if direction.is_some_and(|c| d == c || d == c.opposite()) {
+            continue;
         }
-    }
-}
 
-fn index(direction: &(i32, i32)) -> usize {
-    match direction {
-        (1, 0) => 0,
-        (-1, 0) => 1,
-        (0, 1) => 2,
-        (0, -1) => 3,
-        _ => panic!(),
-    }
-}
+        let mut heat = 0;
+        let mut position = position;
 
-// https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm#Using_a_priority_queue
-fn dijkstra(blocks: &[Vec<u32>], least: usize, most: usize) -> u32 {
-    let (w, h) = (blocks[0].len(), blocks.len());
+        for i in 0..most {
+            position += d.vector();
 
-    let mut heap = BinaryHeap::new();
-    let mut heats = vec![vec![[std::u32::MAX; 4]; w]; h];
+            let Some(block) = blocks.get(position) else {
+                break;
+            };
 
-    // start
-    heats[0][0] = [0, 0, 0, 0];
-    heap.push(Crucible::new((0, 0), (0, 0), 0));
+            heat += block;
 
-    while let Some(crucible) = heap.pop() {
-        // end
-        if crucible.position == (w as i32 - 1, h as i32 - 1) {
-            return crucible.heat;
-        }
-
-        // get all next positions
-        for d in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
-            if d == crucible.direction || d == (-crucible.direction.0, -crucible.direction.1) {
-                continue;
+            if i + 1 >= least {
+                moves.push(((position, Some(d)), heat));
             }
+        }
+    }
+    moves
+}
 
-            let mut heat = crucible.heat;
-            let (mut x, mut y) = crucible.position;
-
-            for i in 0..most {
-                x += d.0;
-                y += d.1;
-
-                if x < 0 || y < 0 {
-                    break;
-                }
-
-                let (x, y) = (x as usize, y as usize);
+fn least_heat(blocks: &Grid<u32>, least: usize, most: usize) -> Result<u32> {
+    let factory = Point::new(blocks.width() as i64 - 1, blocks.height() as i64 - 1);
+
+    Search::new()
+        .dijkstra(
+            (Point::ORIGIN, None),
+            |&crucible| moves(blocks, crucible, least, most),
+            |&(position, _)| position == factory,
+        )?
+        .map(|found| found.cost)
+        .ok_or_else(|| Error::unsolvable("the factory cannot be reached"))
+}
 
-                if x >= w || y >= h {
-                    break;
-                }
+struct Day17;
 
-                heat += blocks[y][x];
+impl Solution for Day17 {
+    type Parsed = Grid<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-                if i + 1 < least {
-                    continue;
-                }
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let blocks = Grid::parse(lines, |c| (c as char).to_digit(10))?;
 
-                if heat < heats[y][x][index(&d)] {
-                    heats[y][x][index(&d)] = heat;
+        Ok(blocks)
+    }
 
-                    heap.push(Crucible::new((x as i32, y as i32), d, heat));
-                }
-            }
-        }
+    fn part1(blocks: &Self::Parsed) -> Result<u32> {
+        least_heat(blocks, 0, 3)
     }
-    panic!();
-}
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    let blocks = lines
-        .map(|line| {
-            line.unwrap()
-                .chars()
-                .map(|c| (c as u8 - b'0') as u32)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+    fn part2(blocks: &Self::Parsed) -> Result<u32> {
+        least_heat(blocks, 4, 10)
+    }
 
-    (dijkstra(&blocks, 0, 3), dijkstra(&blocks, 4, 10))
+    fn validator() -> Validator {
+        Validator::new().grid().alphabet("0123456789")
+    }
 }
 
 #[test]
@@ -131,15 +98,23 @@ fn check() {
 4322674655533";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (102, 94));
+    assert_eq!(
+        Day17::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (102, 94)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a blank in the heat loss map
+    assert!(matches!(
+        Day17::validator().check("2413432311323\n32154 3535623"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: Some(6),
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day17>(file!()) }
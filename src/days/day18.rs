@@ -1,25 +1,28 @@
+use advent_2023::{AtLine, Direction, Error, Point, Result, Solution, Validator};
 use std::io::{BufRead, Lines};
 
-fn double_surface(p1: &(i64, i64), p2: &(i64, i64)) -> i64 {
-    p2.0 * p1.1 - p2.1 * p1.0
+fn double_surface(p1: Point, p2: Point) -> i64 {
+    (p2 - Point::ORIGIN).cross(p1 - Point::ORIGIN)
 }
 
 #[test]
 fn check_surface() {
-    assert_eq!(double_surface(&(0, 2), &(3, 2)), 6);
-    assert_eq!(double_surface(&(3, 2), &(0, 2)), -6);
+    let surface = |(x1, y1), (x2, y2)| double_surface(Point::new(x1, y1), Point::new(x2, y2));
 
-    assert_eq!(double_surface(&(2, 3), &(6, 3)), 12);
-    assert_eq!(double_surface(&(6, 3), &(2, 3)), -12);
+    assert_eq!(surface((0, 2), (3, 2)), 6);
+    assert_eq!(surface((3, 2), (0, 2)), -6);
 
-    assert_eq!(double_surface(&(3, 0), &(3, -2)), 6);
-    assert_eq!(double_surface(&(3, -2), &(3, 0)), -6);
+    assert_eq!(surface((2, 3), (6, 3)), 12);
+    assert_eq!(surface((6, 3), (2, 3)), -12);
+
+    assert_eq!(surface((3, 0), (3, -2)), 6);
+    assert_eq!(surface((3, -2), (3, 0)), -6);
 
     let square = [(2, 0), (6, 0), (6, -5), (2, -5), (2, 0)];
     let s: i64 = square
         .iter()
         .zip(square.iter().skip(1))
-        .map(|(p1, p2)| double_surface(p1, p2))
+        .map(|(&p1, &p2)| surface(p1, p2))
         .sum();
 
     assert_eq!(s, 40);
@@ -28,46 +31,50 @@ fn check_surface() {
     let s: i64 = square
         .iter()
         .zip(square.iter().skip(1))
-        .map(|(p1, p2)| double_surface(p1, p2))
+        .map(|(&p1, &p2)| surface(p1, p2))
         .sum();
 
     assert_eq!(s, 50);
 }
 
-fn color_to_order(hexa: &[u8]) -> ((i64, i64), i64) {
+fn color_to_order(hexa: &[u8]) -> Option<Order> {
     let len = hexa.len();
     let mut v = 0;
 
+    if len < 4 {
+        return None;
+    }
+
     for &c in &hexa[2..len - 2] {
         v = 16 * v
             + match c {
                 b'a'..=b'f' => (10 + c - b'a') as i64,
                 b'0'..=b'9' => (c - b'0') as i64,
-                _ => panic!(),
+                _ => return None,
             };
     }
 
-    (
+    Some((
         match &hexa[len - 2] {
-            b'0' => (1, 0),
-            b'1' => (0, 1),
-            b'2' => (-1, 0),
-            b'3' => (0, -1),
-            _ => panic!(),
+            b'0' => Direction::Right,
+            b'1' => Direction::Down,
+            b'2' => Direction::Left,
+            b'3' => Direction::Up,
+            _ => return None,
         },
         v,
-    )
+    ))
 }
 
 struct Part {
-    digger: (i64, i64),
+    digger: Point,
     double_surface: i64,
     perimeter: i64,
 }
 
 impl Part {
     fn new() -> Self {
-        let digger = (0, 0);
+        let digger = Point::ORIGIN;
         let double_surface = 0;
         let perimeter = 0;
 
@@ -78,13 +85,10 @@ impl Part {
         }
     }
 
-    fn dig(&mut self, direction: (i64, i64), steps: i64) {
-        let next = (
-            self.digger.0 + steps * direction.0,
-            self.digger.1 + steps * direction.1,
-        );
+    fn dig(&mut self, direction: Direction, steps: i64) {
+        let next = self.digger + direction.vector() * steps;
 
-        self.double_surface += double_surface(&self.digger, &next);
+        self.double_surface += double_surface(self.digger, next);
         self.perimeter += steps;
         self.digger = next;
     }
@@ -94,33 +98,70 @@ impl Part {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
-    let mut part1 = Part::new();
-    let mut part2 = Part::new();
-
-    for line in lines {
-        let line = line.unwrap();
-        let v = line.split_whitespace().collect::<Vec<_>>();
-        let direction = match v[0] {
-            "R" => (1, 0),
-            "L" => (-1, 0),
-            "U" => (0, -1),
-            "D" => (0, 1),
-            _ => panic!(),
-        };
-        let steps = v[1].parse::<i64>().unwrap();
-
-        part1.dig(direction, steps);
-
-        let (direction, steps) = color_to_order(v[2].as_bytes());
-
-        part2.dig(direction, steps);
+// a direction and a number of steps
+type Order = (Direction, i64);
+
+fn dig(orders: impl Iterator<Item = Order>) -> i64 {
+    let mut part = Part::new();
+
+    for (direction, steps) in orders {
+        part.dig(direction, steps);
     }
 
-    (part1.result(), part2.result())
+    part.result()
+}
+
+struct Day18;
+
+impl Solution for Day18 {
+    // the plan as read, and as decoded from the colors
+    type Parsed = Vec<(Order, Order)>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line?;
+                let v = line.split_whitespace().collect::<Vec<_>>();
+
+                if v.len() != 3 {
+                    return Err(Error::parse(
+                        index,
+                        "expected `<direction> <steps> <color>`",
+                    ));
+                }
+
+                let direction = v[0]
+                    .parse::<char>()
+                    .ok()
+                    .filter(|c| "RLUD".contains(*c))
+                    .and_then(Direction::from_char)
+                    .ok_or_else(|| Error::parse(index, format!("unknown direction {}", v[0])))?;
+                let steps = v[1].parse::<i64>().at_line(index)?;
+                let color = color_to_order(v[2].as_bytes())
+                    .ok_or_else(|| Error::parse(index, format!("invalid color {}", v[2])))?;
+
+                Ok(((direction, steps), color))
+            })
+            .collect()
+    }
+
+    fn part1(plan: &Self::Parsed) -> Result<i64> {
+        Ok(dig(plan.iter().map(|&(order, _)| order)))
+    }
+
+    fn part2(plan: &Self::Parsed) -> Result<i64> {
+        Ok(dig(plan.iter().map(|&(_, color)| color)))
+    }
+
+    fn validator() -> Validator {
+        Validator::new().lines(r"^[RLUD] \d+ \(#[0-9a-f]{6}\)$")
+    }
 }
 
 #[test]
@@ -141,15 +182,23 @@ L 2 (#015232)
 U 2 (#7a21e3)";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (62, 952408144115));
+    assert_eq!(
+        Day18::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (62, 952408144115)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_validator() {
+    // a color with 5 digits
+    assert!(matches!(
+        Day18::validator().check("R 6 (#70c710)\nD 5 (#0dc57)"),
+        Err(Error::Invalid {
+            line: Some(2),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day18>(file!()) }
@@ -1,61 +1,11 @@
-use advent_2023::Paragrapher;
-use std::collections::HashMap;
+use advent_2023::{
+    AtLine, Error, Hyperrectangle, Interval, Paragrapher, Result, Solution, Validator,
+};
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, Lines};
 
 type Shape = [u64; 4];
-type Range = [(u64, u64); 4];
-
-fn combinations(range: &Range) -> u64 {
-    range.iter().map(|(a, b)| b - a).product()
-}
-
-fn range_empty(range: &Range) -> bool {
-    range.iter().all(|(a, b)| a == b)
-}
-
-type Interval = Option<(u64, u64)>;
-
-fn interval_split_greater(a: u64, b: u64, value: u64) -> (Interval, Interval) {
-    if value < a {
-        (Some((a, b)), None)
-    } else if value >= b {
-        (None, Some((a, b)))
-    } else {
-        (Some((value + 1, b)), Some((a, value + 1)))
-    }
-}
-
-fn interval_split_less(a: u64, b: u64, value: u64) -> (Interval, Interval) {
-    if value <= a {
-        (None, Some((a, b)))
-    } else if value >= b {
-        (Some((a, b)), None)
-    } else {
-        (Some((a, value)), Some((value, b)))
-    }
-}
-
-#[test]
-fn check_interval() {
-    assert_eq!(interval_split_greater(10, 40, 5), (Some((10, 40)), None));
-    assert_eq!(
-        interval_split_greater(10, 40, 10),
-        (Some((11, 40)), Some((10, 11)))
-    );
-    assert_eq!(
-        interval_split_greater(10, 40, 20),
-        (Some((21, 40)), Some((10, 21)))
-    );
-    assert_eq!(interval_split_greater(10, 40, 40), (None, Some((10, 40))));
-
-    assert_eq!(interval_split_less(10, 40, 5), (None, Some((10, 40))));
-    assert_eq!(interval_split_less(10, 40, 10), (None, Some((10, 40))));
-    assert_eq!(
-        interval_split_less(10, 40, 20),
-        (Some((10, 20)), Some((20, 40)))
-    );
-    assert_eq!(interval_split_less(10, 40, 40), (Some((10, 40)), None));
-}
+type Range = Hyperrectangle<u64, 4>;
 
 #[derive(Debug)]
 struct Condition {
@@ -65,24 +15,31 @@ struct Condition {
 }
 
 impl Condition {
-    fn new(condition: &[u8]) -> Self {
-        let index = match condition[0] {
+    fn new(condition: &[u8]) -> Option<Self> {
+        let index = match condition.first()? {
             b'x' => 0,
             b'm' => 1,
             b'a' => 2,
             b's' => 3,
-            _ => panic!(),
+            _ => return None,
         };
-        let value = condition[2..]
-            .iter()
-            .fold(0, |v, c| 10 * v + (c - b'0') as u64);
-        let is_greater = condition[1] == b'>';
-
-        Condition {
+        let is_greater = match condition.get(1)? {
+            b'>' => true,
+            b'<' => false,
+            _ => return None,
+        };
+        // the ratings fit in a u16, so value + 1 cannot overflow in split
+        let value = std::str::from_utf8(&condition[2..])
+            .ok()?
+            .parse::<u16>()
+            .ok()?
+            .into();
+
+        Some(Condition {
             index,
             value,
             is_greater,
-        }
+        })
     }
 
     fn is_ok(&self, shape: &Shape) -> bool {
@@ -93,26 +50,56 @@ impl Condition {
         }
     }
 
-    fn split(&self, range: &Range) -> (Range, Range) {
-        let mut ok = *range;
-        let mut nok = *range;
-        let (a, b);
-
+    // the part of the range that goes on, and the part that does not
+    fn split(&self, range: &Range) -> (Option<Range>, Option<Range>) {
         if self.is_greater {
-            (a, b) = interval_split_greater(range[self.index].0, range[self.index].1, self.value);
+            let (nok, ok) = range.split_at(self.index, self.value + 1);
+
+            (ok, nok)
         } else {
-            (a, b) = interval_split_less(range[self.index].0, range[self.index].1, self.value);
+            range.split_at(self.index, self.value)
         }
+    }
+}
 
-        if let Some(a) = a {
-            ok[self.index] = a;
+#[test]
+fn check_split() {
+    let range = Range::new([Interval::new(10, 40); 4]);
+    let with = |start, end| {
+        let mut r = range;
+
+        r.0[1] = Interval::new(start, end);
+        Some(r)
+    };
+    let greater = |value| {
+        Condition {
+            index: 1,
+            value,
+            is_greater: true,
         }
-        if let Some(b) = b {
-            nok[self.index] = b;
+        .split(&range)
+    };
+    let less = |value| {
+        Condition {
+            index: 1,
+            value,
+            is_greater: false,
         }
+        .split(&range)
+    };
 
-        (ok, nok)
-    }
+    assert_eq!(greater(5), (Some(range), None));
+    assert_eq!(greater(10), (with(11, 40), with(10, 11)));
+    assert_eq!(greater(20), (with(21, 40), with(10, 21)));
+    assert_eq!(greater(40), (None, Some(range)));
+
+    assert_eq!(less(5), (None, Some(range)));
+    assert_eq!(less(10), (None, Some(range)));
+    assert_eq!(less(20), (with(10, 20), with(20, 40)));
+    assert_eq!(less(40), (Some(range), None));
+
+    assert!(Condition::new(b"x>4000").is_some());
+    assert!(Condition::new(b"x>18446744073709551615").is_none());
 }
 
 #[derive(Debug)]
@@ -147,13 +134,13 @@ impl Rule {
         for (c, name) in self.conditions.iter() {
             let (ok, nok) = c.split(&r);
 
-            if !range_empty(&ok) {
+            if let Some(ok) = ok {
                 rs.push((name, ok));
             }
 
-            if range_empty(&nok) {
+            let Some(nok) = nok else {
                 return rs;
-            }
+            };
 
             r = nok;
         }
@@ -164,97 +151,153 @@ impl Rule {
     }
 }
 
-fn is_accepted_shape(rules: &HashMap<String, Rule>, shape: &Shape) -> bool {
+fn get_rule<'a>(rules: &'a HashMap<String, Rule>, name: &str) -> Result<&'a Rule> {
+    rules
+        .get(name)
+        .ok_or_else(|| Error::unsolvable(format!("unknown workflow {name}")))
+}
+
+// a workflow visited twice means the shape goes round forever
+fn is_accepted_shape(rules: &HashMap<String, Rule>, shape: &Shape) -> Result<bool> {
     let mut name = "in";
+    let mut visited = HashSet::new();
 
     loop {
-        name = rules.get(name).unwrap().destination(shape);
+        if !visited.insert(name) {
+            return Err(Error::unsolvable(format!("workflow {name} loops")));
+        }
+
+        name = get_rule(rules, name)?.destination(shape);
 
         match name {
-            "A" => return true,
+            "A" => return Ok(true),
             "R" => break,
             _ => {}
         }
     }
-    false
+    Ok(false)
 }
 
-fn part2(rules: &HashMap<String, Rule>) -> u64 {
+// a range going through more workflows than there are has visited one twice,
+// the shapes it holds go round forever
+fn part2(rules: &HashMap<String, Rule>) -> Result<u64> {
     let starting = String::from("in");
-    let mut ranges: Vec<(&String, Range)> = vec![(&starting, [(1, 4001); 4])];
+    let mut ranges: Vec<(&String, Range, usize)> =
+        vec![(&starting, Range::new([Interval::new(1, 4001); 4]), 0)];
     let mut count = 0;
 
-    while let Some((name, range)) = ranges.pop() {
-        for (n, r) in rules.get(name).unwrap().destinations(&range).into_iter() {
+    while let Some((name, range, depth)) = ranges.pop() {
+        if depth > rules.len() {
+            return Err(Error::unsolvable(format!("workflow {name} loops")));
+        }
+
+        for (n, r) in get_rule(rules, name)?.destinations(&range).into_iter() {
             match n.as_str() {
-                "A" => count += combinations(&r),
+                "A" => count += r.volume(),
                 "R" => {}
-                _ => ranges.push((n, r)),
+                _ => ranges.push((n, r, depth + 1)),
             }
         }
     }
 
-    count
+    Ok(count)
 }
 
-fn resolve<T>(mut lines: Lines<T>) -> (u64, u64)
-where
-    T: BufRead,
-{
-    let mut para_iter = lines.split_paragraph(|l| l);
-
-    let rules = para_iter
-        .next()
-        .unwrap()
-        .iter()
-        .map(|line| {
-            let mut cs = vec![];
-            let i = line.find('{').unwrap();
-            let name = &line[0..i];
-
-            for r in line[i + 1..line.len() - 1].split(',') {
-                if let Some(i) = r.find(':') {
-                    let condition = &r[0..i];
-                    let destination = &r[i + 1..];
-
-                    cs.push((
-                        Condition::new(condition.as_bytes()),
-                        destination.to_string(),
-                    ));
-                } else {
-                    return (name.to_string(), Rule::new(r, cs));
+struct System {
+    rules: HashMap<String, Rule>,
+    shapes: Vec<Shape>,
+}
+
+struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = System;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<T>(mut lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        let mut para_iter = lines.split_paragraph(|l| l);
+
+        let workflows = para_iter.next().at_line(0)??;
+        let rules = workflows
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let mut cs = vec![];
+                let i = line.find('{').at_line(index)?;
+                let name = &line[0..i];
+                let rules = line[i + 1..]
+                    .strip_suffix('}')
+                    .ok_or_else(|| Error::parse(index, "missing closing brace"))?;
+
+                for r in rules.split(',') {
+                    if let Some(i) = r.find(':') {
+                        let condition = &r[0..i];
+                        let destination = &r[i + 1..];
+
+                        cs.push((
+                            Condition::new(condition.as_bytes()).ok_or_else(|| {
+                                Error::parse(index, format!("invalid condition {condition}"))
+                            })?,
+                            destination.to_string(),
+                        ));
+                    } else {
+                        return Ok((name.to_string(), Rule::new(r, cs)));
+                    }
                 }
+                Err(Error::parse(index, "missing default destination"))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        // workflows and the empty line
+        let offset = workflows.len() + 1;
+        let shapes = para_iter
+            .next()
+            .at_line(offset)??
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let mut s: Shape = [0; 4];
+
+                for (idx, v) in line
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|s| !s.is_empty())
+                    .enumerate()
+                {
+                    *s.get_mut(idx).at_line(offset + index)? =
+                        v.parse::<u16>().at_line(offset + index)?.into();
+                }
+                Ok(s)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(System { rules, shapes })
+    }
+
+    fn part1(system: &Self::Parsed) -> Result<u64> {
+        let mut part1 = 0;
+
+        for s in system.shapes.iter() {
+            if is_accepted_shape(&system.rules, s)? {
+                part1 += s.iter().sum::<u64>();
             }
-            panic!();
-        })
-        .collect::<HashMap<_, _>>();
-
-    let shapes = para_iter
-        .next()
-        .unwrap()
-        .iter()
-        .map(|line| {
-            let mut s: Shape = [0; 4];
-
-            for (idx, v) in line
-                .split(|c: char| !c.is_ascii_digit())
-                .filter(|s| !s.is_empty())
-                .enumerate()
-            {
-                s[idx] = v.parse::<u64>().unwrap();
-            }
-            s
-        })
-        .collect::<Vec<_>>();
-
-    (
-        shapes
-            .into_iter()
-            .filter(|s| is_accepted_shape(&rules, s))
-            .flatten()
-            .sum(),
-        part2(&rules),
-    )
+        }
+
+        Ok(part1)
+    }
+
+    fn part2(system: &Self::Parsed) -> Result<u64> {
+        part2(&system.rules)
+    }
+
+    fn validator() -> Validator {
+        Validator::new().paragraphs().lines(
+            r"^([a-z]+\{([xmas][<>]\d+:([a-z]+|A|R),)*([a-z]+|A|R)\}|\{x=\d+,m=\d+,a=\d+,s=\d+\})$",
+        )
+    }
 }
 
 #[test]
@@ -278,15 +321,51 @@ hdj{m>838:A,pv}
 {x=2127,m=1623,a=2188,s=1013}";
     use std::io::Cursor;
 
-    assert_eq!(resolve(Cursor::new(TEST).lines()), (19114, 167409079868000));
+    assert_eq!(
+        Day19::resolve(Cursor::new(TEST).lines()).unwrap(),
+        (19114, 167409079868000)
+    );
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
-    (solution.0.to_string(), solution.1.to_string())
+#[test]
+fn check_overflow() {
+    use std::io::Cursor;
+
+    for input in [
+        "in{x>18446744073709551615:A,R}\n\n{x=1,m=1,a=1,s=1}",
+        "in{x>1:A,R}\n\n{x=18446744073709551616,m=1,a=1,s=1}",
+    ] {
+        assert!(matches!(
+            Day19::parse(Cursor::new(input).lines()),
+            Err(Error::Parse { .. })
+        ));
+    }
+}
+
+#[test]
+fn check_loop() {
+    use std::io::Cursor;
+
+    for workflows in ["in{in}", "in{x>1:in,in}", "in{x>1:ab,R}\nab{in}"] {
+        let input = format!("{workflows}\n\n{{x=2,m=1,a=1,s=1}}");
+        let system = Day19::parse(Cursor::new(input).lines()).unwrap();
+
+        assert!(matches!(Day19::part1(&system), Err(Error::Unsolvable(_))));
+        assert!(matches!(Day19::part2(&system), Err(Error::Unsolvable(_))));
+    }
+}
+
+#[test]
+fn check_validator() {
+    // a part without its s rating
+    assert!(matches!(
+        Day19::validator().check("in{s<1351:px,qqz}\n\n{x=787,m=2655,a=1222}"),
+        Err(Error::Invalid {
+            line: Some(3),
+            column: None,
+            ..
+        })
+    ));
 }
 
-inventory::submit! { advent_2023::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2023::Day::new::<Day19>(file!()) }
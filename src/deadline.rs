@@ -0,0 +1,57 @@
+use crate::{Error, Result};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+// the deadline of the day solved on the current thread, a solver cannot be
+// killed so the long running ones call check_deadline() in their loops, the
+// others are stopped between their phases
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+pub fn check_deadline() -> Result<()> {
+    match DEADLINE.get() {
+        Some(deadline) if Instant::now() >= deadline => Err(Error::TimedOut),
+        _ => Ok(()),
+    }
+}
+
+pub fn with_deadline<R>(timeout: Option<Duration>, f: impl FnOnce() -> R) -> R {
+    let previous = DEADLINE.replace(timeout.map(|t| Instant::now() + t));
+    let result = f();
+
+    DEADLINE.set(previous);
+    result
+}
+
+// a number followed by ms, s or m
+pub fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let position = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(position);
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("invalid duration {s}"))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_secs_f64(value / 1000.0)),
+        "s" | "" => Ok(Duration::from_secs_f64(value)),
+        "m" => Ok(Duration::from_secs_f64(value * 60.0)),
+        _ => Err(format!("invalid duration unit {unit}, expected ms, s or m")),
+    }
+}
+
+#[test]
+fn check_deadline_expiry() {
+    assert!(with_deadline(Some(Duration::ZERO), check_deadline).is_err());
+    assert!(with_deadline(Some(Duration::from_secs(60)), check_deadline).is_ok());
+    assert!(with_deadline(None, check_deadline).is_ok());
+    assert!(check_deadline().is_ok());
+    assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+    assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+    assert_eq!(parse_duration("1m"), Ok(Duration::from_secs(60)));
+    assert!(parse_duration("1h").is_err());
+    assert!(parse_duration("s").is_err());
+}
@@ -0,0 +1,95 @@
+use crate::error::DownloadError;
+use crate::http::{self, Response};
+use crate::{Config, Result};
+use std::fs;
+
+// the input is only written once fully received and checked, a deleted
+// input is restored from the HTTP cache without a request
+pub fn download_input(config: &Config, session: &str, day_number: u32) -> Result<()> {
+    let url = format!("{}/input", config.day_url(day_number));
+    let check = |response: &Response| -> Result<()> {
+        if response.text().contains("Please log in") {
+            return Err(DownloadError::NotLoggedIn.into());
+        }
+
+        match response.status {
+            200 => {}
+            404 => return Err(DownloadError::NotAvailable.into()),
+            status => {
+                return Err(DownloadError::Http {
+                    url: url.clone(),
+                    status,
+                }
+                .into())
+            }
+        }
+
+        if response.body.is_empty() {
+            return Err(DownloadError::Empty.into());
+        }
+
+        Ok(())
+    };
+
+    let Response { body, .. } = http::get(config, &url, session, check)?;
+
+    fs::create_dir_all(&config.input_dir)?;
+    http::write_atomic(&config.input_path(day_number), &body)
+}
+
+#[test]
+fn check_download_input() {
+    use crate::mock::{temp_dir, MockServer};
+    use crate::Error;
+
+    let dir = temp_dir("download");
+    let server = MockServer::start(vec![
+        (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        ),
+        (500, "Internal Server Error"),
+        (200, ""),
+        (200, "0 3 6 9 12 15\n"),
+    ]);
+    let config = crate::mock::config(&server.url, &dir);
+
+    assert!(matches!(
+        download_input(&config, "cookie", 9),
+        Err(Error::Download(DownloadError::NotLoggedIn))
+    ));
+    assert!(matches!(
+        download_input(&config, "cookie", 9),
+        Err(Error::Download(DownloadError::Http { status: 500, .. }))
+    ));
+    assert!(matches!(
+        download_input(&config, "cookie", 9),
+        Err(Error::Download(DownloadError::Empty))
+    ));
+    assert!(!config.input_path(9).exists());
+
+    download_input(&config, "cookie", 9).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(config.input_path(9)).unwrap(),
+        "0 3 6 9 12 15\n"
+    );
+    assert!(!config.input_path(9).with_extension("txt.part").exists());
+
+    // a deleted input comes back from the cache, the server is not asked
+    fs::remove_file(config.input_path(9)).unwrap();
+    download_input(&config, "cookie", 9).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(config.input_path(9)).unwrap(),
+        "0 3 6 9 12 15\n"
+    );
+
+    let requests = server.requests();
+
+    assert_eq!(requests.len(), 4);
+
+    assert!(requests[0].starts_with("GET /2023/day/9/input "));
+    assert!(requests[0].contains(&format!("User-Agent: {}", crate::USER_AGENT)));
+    assert!(requests[0].contains("session=cookie"));
+}
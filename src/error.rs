@@ -0,0 +1,156 @@
+use std::fmt::{self, Display};
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Config(String),
+    Network(curl::Error),
+    Download(DownloadError),
+    Submit(String),
+    Parse {
+        line: usize,
+        reason: String,
+    },
+    Unsolvable(String),
+    TimedOut,
+    NotImplemented(u32),
+    Invalid {
+        line: Option<usize>,
+        column: Option<usize>,
+        reason: String,
+    },
+}
+
+#[derive(Debug)]
+pub enum DownloadError {
+    NoSession,
+    NotLoggedIn,
+    NotAvailable,
+    Http { url: String, status: u32 },
+    Empty,
+    Offline,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // index is the 0-based line index as given by enumerate()
+    pub fn parse(index: usize, reason: impl Into<String>) -> Self {
+        Error::Parse {
+            line: index + 1,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn unsolvable(reason: impl Into<String>) -> Self {
+        Error::Unsolvable(reason.into())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {e}"),
+            Error::Config(reason) => write!(f, "invalid configuration: {reason}"),
+            Error::Network(e) => write!(f, "network error: {e}"),
+            Error::Download(reason) => write!(f, "download failed: {reason}"),
+            Error::Submit(reason) => write!(f, "submission failed: {reason}"),
+            Error::Parse { line, reason } => write!(f, "parse error at line {line}: {reason}"),
+            Error::Unsolvable(reason) => write!(f, "unsolvable input: {reason}"),
+            Error::TimedOut => write!(f, "timed out"),
+            Error::NotImplemented(day_number) => write!(f, "day {day_number} is not implemented"),
+            Error::Invalid {
+                line: Some(line),
+                column: Some(column),
+                reason,
+            } => write!(f, "invalid input at line {line}, column {column}: {reason}"),
+            Error::Invalid {
+                line: Some(line),
+                reason,
+                ..
+            } => write!(f, "invalid input at line {line}: {reason}"),
+            Error::Invalid { reason, .. } => write!(f, "invalid input: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DownloadError::NoSession => write!(
+                f,
+                "no session token, use --session, AOC_SESSION or ~/.config/aoc/session"
+            ),
+            DownloadError::NotLoggedIn => write!(
+                f,
+                "the server asks to log in, the session token (from --session, AOC_SESSION or the session file) is invalid or expired"
+            ),
+            DownloadError::NotAvailable => write!(f, "the puzzle is not available yet"),
+            DownloadError::Http { url, status } => write!(f, "{url} answered HTTP {status}"),
+            DownloadError::Empty => write!(f, "the server sent an empty input"),
+            DownloadError::Offline => write!(f, "not in the cache and working offline"),
+        }
+    }
+}
+
+impl From<DownloadError> for Error {
+    fn from(e: DownloadError) -> Self {
+        Error::Download(e)
+    }
+}
+
+impl From<curl::Error> for Error {
+    fn from(e: curl::Error) -> Self {
+        Error::Network(e)
+    }
+}
+
+pub trait AtLine<T> {
+    fn at_line(self, index: usize) -> Result<T>;
+}
+
+impl<T, E> AtLine<T> for std::result::Result<T, E>
+where
+    E: Display,
+{
+    fn at_line(self, index: usize) -> Result<T> {
+        self.map_err(|e| Error::parse(index, e.to_string()))
+    }
+}
+
+impl<T> AtLine<T> for Option<T> {
+    fn at_line(self, index: usize) -> Result<T> {
+        self.ok_or_else(|| Error::parse(index, "malformed line"))
+    }
+}
+
+#[test]
+fn check_at_line() {
+    let e = "x1".parse::<u32>().at_line(2).unwrap_err();
+
+    assert_eq!(
+        e.to_string(),
+        "parse error at line 3: invalid digit found in string"
+    );
+    assert!(matches!(
+        None::<u32>.at_line(0),
+        Err(Error::Parse { line: 1, .. })
+    ));
+}
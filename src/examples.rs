@@ -0,0 +1,195 @@
+use crate::answers::Answers;
+use crate::puzzle::{articles, tokens, Token};
+use crate::Result;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// puzzles/NN-k.txt holds the k-th example input of a day, the expected
+// answers being in puzzles/NN-k.expected with the format of the answers,
+// the directory is not named examples as cargo takes it for example targets
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+// the last <pre> block of an article and the last emphasized code outside
+// of it, which is where the puzzles give the example and its answer
+fn last_example(article: &str) -> (Option<String>, Option<String>) {
+    let (mut block, mut answer) = (None, None);
+    let (mut in_pre, mut in_code, mut in_em) = (false, false, false);
+    let (mut text, mut code) = (String::new(), String::new());
+
+    for token in tokens(article) {
+        match token {
+            Token::Open("pre", _) => {
+                in_pre = true;
+                text.clear();
+            }
+            Token::Close("pre") => {
+                in_pre = false;
+                block = Some(text.clone());
+            }
+            Token::Text(t) if in_pre => text.push_str(&t),
+            Token::Open("code", _) if !in_pre => {
+                in_code = true;
+                code.clear();
+            }
+            Token::Close("code") if !in_pre => {
+                in_code = false;
+                if !code.trim().is_empty() {
+                    answer = Some(code.trim().to_string());
+                }
+            }
+            Token::Open("em", _) => in_em = true,
+            Token::Close("em") => in_em = false,
+            Token::Text(t) if in_code && in_em => code.push_str(&t),
+            _ => {}
+        }
+    }
+
+    let block = block
+        .filter(|b| !b.trim().is_empty())
+        .map(|b| format!("{}\n", b.trim_end_matches('\n')));
+
+    (block, answer)
+}
+
+// part 2 either brings its own example or reuses the one of part 1
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (part, article) in articles(html).into_iter().take(2).enumerate() {
+        let (input, answer) = match last_example(article) {
+            (input, Some(answer)) => (input, answer),
+            (_, None) => continue,
+        };
+        let example = match input {
+            Some(input) => {
+                examples.push(Example {
+                    input,
+                    answers: Answers::default(),
+                });
+                examples.last_mut()
+            }
+            None => examples.last_mut(),
+        };
+
+        if let Some(example) = example {
+            if part == 0 {
+                example.answers.part1 = Some(answer);
+            } else {
+                example.answers.part2 = Some(answer);
+            }
+        }
+    }
+
+    examples
+}
+
+fn paths(dir: &Path, day_number: u32) -> Result<Vec<PathBuf>> {
+    let prefix = format!("{day_number:0>2}-");
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let mut paths = vec![];
+
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        if name.starts_with(&prefix) && name.ends_with(".txt") {
+            paths.push(path);
+        }
+    }
+
+    paths.sort_by_key(|p| {
+        p.file_stem()
+            .and_then(|s| s.to_str()?.split_once('-')?.1.parse::<u32>().ok())
+    });
+
+    Ok(paths)
+}
+
+// the input files and their expected answers, in order
+pub fn load(dir: &Path, day_number: u32) -> Result<Vec<(PathBuf, Answers)>> {
+    paths(dir, day_number)?
+        .into_iter()
+        .map(|path| {
+            let answers = Answers::read(&path.with_extension("expected"))?;
+
+            Ok((path, answers))
+        })
+        .collect()
+}
+
+// the examples of a day are replaced as a whole
+pub fn save(dir: &Path, day_number: u32, examples: &[Example]) -> Result<()> {
+    for path in paths(dir, day_number)? {
+        fs::remove_file(path.with_extension("expected")).or_else(|e| match e.kind() {
+            io::ErrorKind::NotFound => Ok(()),
+            _ => Err(e),
+        })?;
+        fs::remove_file(path)?;
+    }
+
+    fs::create_dir_all(dir)?;
+
+    for (k, example) in examples.iter().enumerate() {
+        let path = dir.join(format!("{day_number:0>2}-{}.txt", k + 1));
+
+        fs::write(&path, &example.input)?;
+        example.answers.write(&path.with_extension("expected"))?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn check_extract() {
+    const PUZZLE: &str = include_str!("../fixtures/puzzle.html");
+
+    assert_eq!(
+        extract(PUZZLE),
+        [Example {
+            input: "1 2\n3 4\n5 <6>\n".to_string(),
+            answers: Answers {
+                part1: Some("21".to_string()),
+                part2: Some("44".to_string()),
+            },
+        }]
+    );
+    assert_eq!(extract("<article><p>No example.</p></article>"), []);
+}
+
+#[test]
+fn check_save_load() {
+    use crate::mock::temp_dir;
+
+    let dir = temp_dir("examples");
+    let example = |input: &str, part2: &str| Example {
+        input: input.to_string(),
+        answers: Answers {
+            part1: None,
+            part2: Some(part2.to_string()),
+        },
+    };
+
+    save(&dir, 3, &[example("a\n", "1"), example("b\n", "2")]).unwrap();
+    save(&dir, 4, &[example("c\n", "3")]).unwrap();
+
+    let examples = load(&dir, 3).unwrap();
+
+    assert_eq!(examples.len(), 2);
+    assert_eq!(fs::read_to_string(&examples[1].0).unwrap(), "b\n");
+    assert_eq!(examples[1].1.part2.as_deref(), Some("2"));
+
+    // saving again drops the stale ones
+    save(&dir, 3, &[example("d\n", "4")]).unwrap();
+
+    assert_eq!(load(&dir, 3).unwrap().len(), 1);
+    assert_eq!(load(&dir, 4).unwrap().len(), 1);
+}
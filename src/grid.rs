@@ -0,0 +1,304 @@
+use crate::{Direction, Error, Point, Result};
+use std::fmt::{self, Display};
+use std::io::{BufRead, Lines};
+use std::ops::{Index, IndexMut};
+
+// a rectangle of cells stored row after row, positions are points with y
+// going down
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    // None when there is no row, an empty row or rows of different lengths
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // one row per line, cell turns each byte into a cell or refuses it
+    pub fn parse<B, F>(lines: Lines<B>, mut cell: F) -> Result<Self>
+    where
+        B: BufRead,
+        F: FnMut(u8) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in lines.enumerate() {
+            let line = line?;
+
+            if *width.get_or_insert(line.len()) != line.len() || line.is_empty() {
+                return Err(Error::parse(y, "the grid is not rectangular"));
+            }
+
+            for c in line.bytes() {
+                let cell = cell(c)
+                    .ok_or_else(|| Error::parse(y, format!("unexpected {:?}", c as char)))?;
+
+                cells.push(cell);
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            None => Err(Error::unsolvable("empty grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // a point next to the border can be checked as is
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.y as usize * self.width + p.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y as usize * self.width + p.x as usize])
+        } else {
+            None
+        }
+    }
+
+    // up, right, down and left of p, when inside the grid
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| p + d.vector())
+            .filter(|&n| self.contains(n))
+    }
+
+    // the diagonals included, clockwise from up
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .flat_map(|d| [d.vector(), d.vector() + d.turn_right().vector()])
+            .map(move |v| p + v)
+            .filter(|&n| self.contains(n))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    // a grid without columns still has its empty rows
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    // panics when x is not a column, like row does for y
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} outside of the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    fn point(&self, i: usize) -> Point {
+        Point::from((i % self.width, i / self.width))
+    }
+
+    // row after row
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point(i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.cells.iter().position(predicate).map(|i| self.point(i))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // the rows become the columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+                .map(|p| self[Point::from(p)].clone())
+                .collect(),
+        }
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+                .map(|p| self[Point::from(p)].clone())
+                .collect(),
+        }
+    }
+}
+
+// panics outside of the grid
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} outside of the grid"))
+    }
+}
+
+// one line per row, the cells are not separated
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn check_grid() {
+    use std::io::Cursor;
+
+    let grid = Grid::parse(Cursor::new("abc\ndef").lines(), |c| Some(c as char)).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+    assert_eq!(grid[Point::new(2, 1)], 'f');
+    assert_eq!(grid.get(Point::new(3, 1)), None);
+    assert_eq!(grid.get(Point::new(-1, 0)), None);
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(grid.column(1).collect::<String>(), "be");
+    assert_eq!(grid.position(|&c| c == 'e'), Some(Point::new(1, 1)));
+    assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &'e')));
+    assert_eq!(
+        grid.neighbours4(Point::ORIGIN).collect::<Vec<_>>(),
+        [Point::new(1, 0), Point::new(0, 1)]
+    );
+    assert_eq!(
+        grid.neighbours8(Point::new(1, 0)).collect::<Vec<_>>(),
+        [
+            Point::new(2, 0),
+            Point::new(2, 1),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(0, 0)
+        ]
+    );
+    assert_eq!(grid.to_string(), "abc\ndef");
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+    assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+    assert_eq!(
+        grid.rotate_clockwise().rotate_counterclockwise(),
+        grid.clone()
+    );
+    assert_eq!(
+        grid.map(|c| c.is_ascii_lowercase()).get(Point::ORIGIN),
+        Some(&true)
+    );
+
+    assert!(Grid::parse(Cursor::new("abc\nde").lines(), Some).is_err());
+    assert!(Grid::parse(Cursor::new("abc\ndxf").lines(), |c| (c != b'x')
+        .then_some(c))
+    .is_err());
+    assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    assert_eq!(Grid::new(0, 2, 0).rows().count(), 2);
+    assert_eq!(Grid::new(0, 2, 0).columns().count(), 0);
+    assert!(std::panic::catch_unwind(|| grid.column(3).count()).is_err());
+    assert_eq!(Grid::new(0, 2, 0).to_string(), "\n");
+    assert_eq!(
+        Grid::from_rows(vec![vec![1, 2], vec![3, 4]])
+            .unwrap()
+            .to_string(),
+        "12\n34"
+    );
+}
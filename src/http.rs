@@ -0,0 +1,270 @@
+use crate::{Config, Result, USER_AGENT};
+use curl::easy::Easy;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// every request of the process goes through this lock so the interval is
+// also respected between threads
+static THROTTLE: Mutex<()> = Mutex::new(());
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u32,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+// the time of the last request is kept in the cache directory so that
+// back to back invocations are throttled too
+fn throttle(config: &Config) -> Result<()> {
+    let path = config.cache_dir().join("last_request");
+    let last = match fs::read_to_string(&path) {
+        Ok(content) => Duration::from_millis(content.trim().parse().unwrap_or(0)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Duration::ZERO,
+        Err(e) => return Err(e.into()),
+    };
+    let elapsed = now().saturating_sub(last);
+
+    if elapsed < config.request_interval {
+        thread::sleep(config.request_interval - elapsed);
+    }
+
+    fs::create_dir_all(config.cache_dir())?;
+    fs::write(path, now().as_millis().to_string())?;
+
+    Ok(())
+}
+
+fn perform(config: &Config, url: &str, session: &str, fields: Option<&str>) -> Result<Response> {
+    let _lock = THROTTLE.lock().unwrap_or_else(|e| e.into_inner());
+
+    throttle(config)?;
+
+    let mut body = vec![];
+    let mut handle = Easy::new();
+
+    handle.url(url)?;
+    handle.useragent(USER_AGENT)?;
+    handle.cookie(&format!("session={session}"))?;
+
+    if let Some(fields) = fields {
+        handle.post(true)?;
+        handle.post_fields_copy(fields.as_bytes())?;
+    }
+
+    {
+        let mut transfer = handle.transfer();
+
+        transfer.write_function(|data| {
+            body.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+
+    Ok(Response {
+        status: handle.response_code()?,
+        body,
+    })
+}
+
+// 64 bits FNV-1a, unlike the std hashers its output does not change with the
+// Rust release so the cache survives a toolchain upgrade
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// a resource is identified by its url and the account fetching it
+fn cache_path(config: &Config, url: &str, session: &str) -> PathBuf {
+    let key = format!("{url}\0{session}");
+
+    config
+        .cache_dir()
+        .join(format!("{:016x}", fnv1a(key.as_bytes())))
+}
+
+fn read_cache(path: &Path) -> Result<Option<Response>> {
+    match fs::read(path.with_extension("body")) {
+        Ok(body) => Ok(Some(Response { status: 200, body })),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// written next to its destination then renamed over it, a reader never sees
+// a partial file
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+
+    partial.push(".part");
+    fs::write(&partial, contents)?;
+    fs::rename(&partial, path)?;
+
+    Ok(())
+}
+
+// the metadata is written first, the body being renamed in place last
+// marks the entry as complete
+fn write_cache(path: &Path, url: &str, response: &Response) -> Result<()> {
+    fs::write(
+        path.with_extension("meta"),
+        format!(
+            "url = \"{url}\"\nstatus = {}\nfetched = {}\nlength = {}\n",
+            response.status,
+            now().as_secs(),
+            response.body.len()
+        ),
+    )?;
+    write_atomic(&path.with_extension("body"), &response.body)
+}
+
+// a response is only cached once check accepted it, an error or a login
+// page is fetched again
+pub fn get(
+    config: &Config,
+    url: &str,
+    session: &str,
+    check: impl Fn(&Response) -> Result<()>,
+) -> Result<Response> {
+    match cached(config, url, session)? {
+        Some(response) => Ok(response),
+        None => refresh(config, url, session, check),
+    }
+}
+
+// the cached response only, without going to the network
+pub fn cached(config: &Config, url: &str, session: &str) -> Result<Option<Response>> {
+    read_cache(&cache_path(config, url, session))
+}
+
+// goes to the network even when cached, the cached response is only replaced
+// once check accepted the new one
+pub fn refresh(
+    config: &Config,
+    url: &str,
+    session: &str,
+    check: impl Fn(&Response) -> Result<()>,
+) -> Result<Response> {
+    let response = perform(config, url, session, None)?;
+
+    check(&response)?;
+    write_cache(&cache_path(config, url, session), url, &response)?;
+
+    Ok(response)
+}
+
+pub fn get_uncached(config: &Config, url: &str, session: &str) -> Result<Response> {
+    perform(config, url, session, None)
+}
+
+pub fn post(config: &Config, url: &str, session: &str, fields: &str) -> Result<Response> {
+    perform(config, url, session, Some(fields))
+}
+
+#[test]
+fn check_cache() {
+    use crate::mock::{temp_dir, MockServer};
+
+    let dir = temp_dir("http-cache");
+    let server = MockServer::start(vec![
+        (200, "Please log in"),
+        (200, "puzzle"),
+        (200, "other"),
+        (200, "again"),
+    ]);
+    let config = Config {
+        cache_dir: Some(dir.clone()),
+        request_interval: Duration::ZERO,
+        ..Config::default()
+    };
+    let url = format!("{}/2023/day/1", server.url);
+    let check = |response: &Response| match response.text().as_str() {
+        "Please log in" => Err(crate::Error::Config("not logged in".to_string())),
+        _ => Ok(()),
+    };
+
+    assert!(get(&config, &url, "cookie", check).is_err());
+    assert_eq!(
+        get(&config, &url, "cookie", check).unwrap().text(),
+        "puzzle"
+    );
+    // served from the cache, no request sent
+    assert_eq!(
+        get(&config, &url, "cookie", check).unwrap().text(),
+        "puzzle"
+    );
+    // another account is another resource
+    assert_eq!(get(&config, &url, "other", check).unwrap().text(), "other");
+
+    assert_eq!(
+        refresh(&config, &url, "cookie", check).unwrap().text(),
+        "again"
+    );
+    assert_eq!(get(&config, &url, "cookie", check).unwrap().text(), "again");
+    assert_eq!(server.requests().len(), 4);
+
+    let meta = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.extension().is_some_and(|e| e == "meta"))
+        .unwrap();
+
+    assert!(fs::read_to_string(meta).unwrap().contains(&url));
+}
+
+#[test]
+fn check_cache_path() {
+    let config = Config {
+        cache_dir: Some(PathBuf::from("cache")),
+        ..Config::default()
+    };
+
+    // the names must not change between builds
+    assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+    assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(
+        cache_path(&config, "https://adventofcode.com/2023/day/1", "cookie"),
+        Path::new("cache").join(format!(
+            "{:016x}",
+            fnv1a(b"https://adventofcode.com/2023/day/1\0cookie")
+        ))
+    );
+}
+
+#[test]
+fn check_throttle() {
+    use crate::mock::{temp_dir, MockServer};
+    use std::time::Instant;
+
+    let server = MockServer::start(vec![(200, ""), (200, "")]);
+    let config = Config {
+        cache_dir: Some(temp_dir("http-throttle")),
+        request_interval: Duration::from_millis(300),
+        ..Config::default()
+    };
+    let start = Instant::now();
+
+    get_uncached(&config, &server.url, "cookie").unwrap();
+    get_uncached(&config, &server.url, "cookie").unwrap();
+
+    // the timestamp is stored in milliseconds
+    assert!(start.elapsed() >= Duration::from_millis(299));
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,308 @@
+use std::iter::{Product, Sum};
+use std::ops::{Add, Sub};
+
+// the values from start included to end excluded, empty when start >= end
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Default,
+    {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    // None when it would be empty
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Interval::new(
+            self.start.max(other.start),
+            self.end.min(other.end),
+        ))
+        .filter(|i| !i.is_empty())
+    }
+
+    // the values below value and the ones from value, None when empty
+    pub fn split_at(&self, value: T) -> (Option<Self>, Option<Self>) {
+        let below = Interval::new(self.start, self.end.min(value));
+        let above = Interval::new(self.start.max(value), self.end);
+
+        (
+            Some(below).filter(|i| !i.is_empty()),
+            Some(above).filter(|i| !i.is_empty()),
+        )
+    }
+
+    pub fn shift(&self, offset: T) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+// sorted intervals, neither empty, overlapping nor touching
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // the number of values
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Default + Sum,
+    {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.union(&IntervalSet::from_iter([interval]));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersection(b)))
+            .collect()
+    }
+
+    // the values of self that are not in other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+
+        for a in self.intervals.iter() {
+            let mut start = a.start;
+
+            for b in other.intervals.iter().filter(|b| b.end > a.start) {
+                if b.start >= a.end {
+                    break;
+                }
+                if b.start > start {
+                    intervals.push(Interval::new(start, b.start));
+                }
+                start = start.max(b.end);
+            }
+            intervals.push(Interval::new(start, a.end));
+        }
+        intervals.into_iter().collect()
+    }
+
+    // the values below value and the ones from value
+    pub fn split_at(&self, value: T) -> (Self, Self) {
+        let (below, above): (Vec<_>, Vec<_>) =
+            self.intervals.iter().map(|i| i.split_at(value)).unzip();
+
+        (
+            below.into_iter().flatten().collect(),
+            above.into_iter().flatten().collect(),
+        )
+    }
+
+    // the values in the source of a piece are shifted by its offset, the
+    // others are kept, the sources must not overlap
+    pub fn map(&self, pieces: &[(Interval<T>, T)]) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        let sources = pieces.iter().map(|&(source, _)| source).collect();
+        let mapped = pieces.iter().flat_map(|(source, offset)| {
+            self.intervals
+                .iter()
+                .filter_map(|i| i.intersection(source))
+                .map(|i| i.shift(*offset))
+        });
+
+        mapped.chain(self.difference(&sources).intervals).collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted = iter
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<_>>();
+        let mut intervals: Vec<Interval<T>> = vec![];
+
+        sorted.sort();
+
+        for i in sorted {
+            match intervals.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => intervals.push(i),
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+// an interval on each of the N axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hyperrectangle<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: Copy + Ord, const N: usize> Hyperrectangle<T, N> {
+    pub fn new(intervals: [Interval<T>; N]) -> Self {
+        Hyperrectangle(intervals)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    // the number of points in it
+    pub fn volume(&self) -> T
+    where
+        T: Sub<Output = T> + Default + Product,
+    {
+        self.0.iter().map(Interval::len).product()
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, &v)| i.contains(v))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut intervals = self.0;
+
+        for (i, b) in intervals.iter_mut().zip(other.0.iter()) {
+            *i = i.intersection(b)?;
+        }
+        Some(Hyperrectangle(intervals))
+    }
+
+    // cut along the axis, below value and from value, None when empty
+    pub fn split_at(&self, axis: usize, value: T) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.0[axis].split_at(value);
+        let with = |interval| {
+            let mut intervals = self.0;
+
+            intervals[axis] = interval;
+            Hyperrectangle(intervals)
+        };
+
+        (below.map(with), above.map(with))
+    }
+}
+
+#[test]
+fn check_interval() {
+    let i = Interval::new(10, 40);
+
+    assert_eq!(i.len(), 30);
+    assert!(i.contains(10) && !i.contains(40));
+    assert_eq!(Interval::new(5, 3).len(), 0);
+    assert_eq!(
+        i.intersection(&Interval::new(30, 50)),
+        Some(Interval::new(30, 40))
+    );
+    assert_eq!(i.intersection(&Interval::new(40, 50)), None);
+    assert_eq!(i.split_at(5), (None, Some(i)));
+    assert_eq!(
+        i.split_at(20),
+        (Some(Interval::new(10, 20)), Some(Interval::new(20, 40)))
+    );
+    assert_eq!(i.split_at(40), (Some(i), None));
+    assert_eq!(i.shift(-10), Interval::new(0, 30));
+}
+
+#[test]
+fn check_interval_set() {
+    let set = |intervals: &[(i64, i64)]| {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect::<IntervalSet<_>>()
+    };
+    let a = set(&[(10, 20), (0, 5), (5, 7), (30, 30)]);
+    let b = set(&[(3, 12), (18, 40)]);
+
+    assert_eq!(a, set(&[(0, 7), (10, 20)]));
+    assert_eq!(a.len(), 17);
+    assert_eq!(a.min(), Some(0));
+    assert!(a.contains(19) && !a.contains(8));
+    assert_eq!(a.union(&b), set(&[(0, 40)]));
+    assert_eq!(a.intersection(&b), set(&[(3, 7), (10, 12), (18, 20)]));
+    assert_eq!(a.difference(&b), set(&[(0, 3), (12, 18)]));
+    assert_eq!(b.difference(&a), set(&[(7, 10), (20, 40)]));
+    assert_eq!(a.split_at(6), (set(&[(0, 6)]), set(&[(6, 7), (10, 20)])));
+
+    let mut c = IntervalSet::new();
+
+    c.insert(Interval::new(1, 2));
+    c.insert(Interval::new(2, 3));
+    assert_eq!(c, set(&[(1, 3)]));
+
+    // 98 and 99 go to 50 and 51, 50 to 97 go to 52 to 99
+    let pieces = [(Interval::new(98, 100), -48), (Interval::new(50, 98), 2)];
+
+    assert_eq!(set(&[(3, 5)]).map(&pieces), set(&[(3, 5)]));
+    assert_eq!(set(&[(55, 60)]).map(&pieces), set(&[(57, 62)]));
+    assert_eq!(set(&[(45, 55)]).map(&pieces), set(&[(45, 50), (52, 57)]));
+    assert_eq!(set(&[(99, 101)]).map(&pieces), set(&[(51, 52), (100, 101)]));
+    assert_eq!(set(&[(45, 195)]).map(&pieces), set(&[(45, 195)]));
+}
+
+#[test]
+fn check_hyperrectangle() {
+    let cube = Hyperrectangle::new([Interval::new(1, 4001); 4]);
+
+    assert_eq!(cube.volume(), 4000_u64.pow(4));
+    assert!(cube.contains(&[1, 4000, 2000, 1]));
+
+    let (below, above) = cube.split_at(2, 1001);
+
+    assert_eq!(below.unwrap().volume(), 1000 * 4000_u64.pow(3));
+    assert_eq!(above.unwrap().volume(), 3000 * 4000_u64.pow(3));
+    assert_eq!(cube.split_at(0, 1), (None, Some(cube)));
+    assert_eq!(below.unwrap().intersection(&cube), Some(below.unwrap()));
+    assert_eq!(below.unwrap().intersection(&above.unwrap()), None);
+    assert!(!cube.is_empty());
+}
@@ -1,10 +1,59 @@
-use curl::easy::Easy;
+use rayon::prelude::*;
+use std::any::Any;
 use std::cmp::{Eq, Ord, Ordering};
-use std::env;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Lines, Write};
+use std::hint;
+use std::io::{self, BufRead, Cursor, Lines, Read};
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+mod answers;
+mod bench;
+mod config;
+mod cycle;
+mod deadline;
+mod download;
+mod error;
+mod examples;
+mod geometry;
+mod grid;
+mod http;
+mod interval;
+#[cfg(test)]
+mod mock;
+mod puzzle;
+mod report;
+mod scaffold;
+mod search;
+mod selection;
+mod session;
+mod solution;
+mod submit;
+mod validator;
+
+use answers::{Answers, Check};
+pub use bench::BenchOptions;
+use bench::{DayBench, Stats};
+pub use config::Config;
+pub use cycle::Cycle;
+pub use deadline::{check_deadline, parse_duration};
+pub use error::{AtLine, DownloadError, Error, Result};
+pub use geometry::{Direction, Point, Vector};
+pub use grid::Grid;
+pub use interval::{Hyperrectangle, Interval, IntervalSet};
+pub use report::Format;
+use report::{Record, Status, Timings};
+pub use search::{Found, Search};
+pub use selection::Selection;
+pub use solution::Solution;
+pub use validator::Validator;
+
+// adventofcode.com asks automated tools to identify themselves
+const USER_AGENT: &str = concat!(
+    "advent_2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/ccalmels/advent_2023)"
+);
 
 pub struct Paragraph<'a, T, F, O>
 where
@@ -18,14 +67,14 @@ pub trait Paragrapher<T, F, O>
 where
     F: Fn(String) -> O,
 {
-    fn split_paragraph(&mut self, transfom: F) -> Paragraph<T, F, O>;
+    fn split_paragraph(&mut self, transfom: F) -> Paragraph<'_, T, F, O>;
 }
 
 impl<T, F, O> Paragrapher<T, F, O> for Lines<T>
 where
     F: Fn(String) -> O,
 {
-    fn split_paragraph(&mut self, transform: F) -> Paragraph<T, F, O> {
+    fn split_paragraph(&mut self, transform: F) -> Paragraph<'_, T, F, O> {
         Paragraph {
             lines: self,
             transform,
@@ -38,16 +87,19 @@ where
     T: BufRead,
     F: Fn(String) -> O,
 {
-    type Item = Vec<O>;
+    type Item = io::Result<Vec<O>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut p = vec![];
 
         for line in self.lines.by_ref() {
-            let line = line.unwrap();
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
 
             if line.is_empty() {
-                return Some(p);
+                return Some(Ok(p));
             } else {
                 p.push((self.transform)(line));
             }
@@ -56,34 +108,114 @@ where
         if p.is_empty() {
             None
         } else {
-            Some(p)
+            Some(Ok(p))
         }
     }
 }
 
+pub type Input = Lines<Box<dyn BufRead>>;
+
 #[derive(Eq)]
 pub struct Day {
     day_filename: &'static str,
-    resolve: fn(Lines<BufReader<File>>) -> (String, String),
+    parse: fn(Input) -> Result<Box<dyn Any>>,
+    part1: fn(&dyn Any) -> Result<String>,
+    part2: fn(&dyn Any) -> Result<String>,
+    validator: fn() -> Validator,
 }
 
 impl Day {
-    pub const fn new(
-        day_filename: &'static str,
-        resolve: fn(Lines<BufReader<File>>) -> (String, String),
-    ) -> Self {
+    pub const fn new<S: Solution>(day_filename: &'static str) -> Self {
         Day {
             day_filename,
-            resolve,
+            parse: solution::parse::<S>,
+            part1: solution::part1::<S>,
+            part2: solution::part2::<S>,
+            validator: S::validator,
         }
     }
 
-    fn print(&self) {
-        let start = Instant::now();
-        let (day_number, part1, part2) = self.resolve();
-        let duration = start.elapsed();
+    fn run(
+        &self,
+        config: &Config,
+        input: Option<&Path>,
+        part: Option<u8>,
+        timeout: Option<Duration>,
+    ) -> Record {
+        let day = self.parse_number();
+        let cpu_start = thread_cpu_time();
+        let solved = self.solve(config, input, part, timeout);
+        let cpu = cpu_start
+            .zip(thread_cpu_time())
+            .map(|(start, end)| end.saturating_sub(start));
+
+        match solved {
+            Ok((answers, timings)) => Record {
+                day,
+                answers,
+                timings: Some(timings),
+                cpu,
+                status: Status::Ok,
+            },
+            Err(e) => Record {
+                day,
+                answers: Answers::default(),
+                timings: None,
+                cpu,
+                status: match (e, timeout) {
+                    (Error::TimedOut, Some(timeout)) => Status::TimedOut(timeout),
+                    (e, _) => Status::Failed(e.to_string()),
+                },
+            },
+        }
+    }
+
+    fn verify(&self, config: &Config, part: Option<u8>) -> bool {
+        let day_number = self.parse_number();
+        let result = self.resolve(config, None, part).and_then(|answers| {
+            Ok(Answers::load(&config.answers_dir, day_number)?.check(&answers))
+        });
+
+        match result {
+            Ok((part1, part2)) => {
+                let ok =
+                    !matches!(part1, Check::Fail { .. }) && !matches!(part2, Check::Fail { .. });
+
+                println!(
+                    "day{day_number:0>2}: part1: {:40} part2: {}",
+                    part1.to_string(),
+                    part2
+                );
+                ok
+            }
+            Err(e) => {
+                println!("day{day_number:0>2}: failed: {e}");
+                false
+            }
+        }
+    }
+
+    fn record(&self, config: &Config) -> bool {
+        let day_number = self.parse_number();
+        let result = self.resolve(config, None, None).and_then(|answers| {
+            answers.save(&config.answers_dir, day_number)?;
+            Ok(answers)
+        });
 
-        println!("day{day_number:0>2}: part1: {part1:20} part2: {part2:20} in {duration:?}");
+        match result {
+            Ok(Answers { part1, part2 }) => {
+                println!(
+                    "day{day_number:0>2}: recorded part1: {:20} part2: {:20}",
+                    part1.unwrap_or_default(),
+                    part2.unwrap_or_default()
+                );
+                true
+            }
+            Err(e) => {
+                println!("day{day_number:0>2}: failed: {e}");
+                false
+            }
+        }
     }
 
     fn parse_number(&self) -> u32 {
@@ -93,10 +225,119 @@ impl Day {
             .unwrap()
     }
 
-    fn resolve(&self) -> (u32, String, String) {
-        let day_number = self.parse_number();
-        let (part1, part2) = (self.resolve)(read_lines(day_number).unwrap());
-        (day_number, part1, part2)
+    // without a part given, both are run, the timeout does not include the
+    // input acquisition
+    fn solve(
+        &self,
+        config: &Config,
+        input: Option<&Path>,
+        part: Option<u8>,
+        timeout: Option<Duration>,
+    ) -> Result<(Answers, Timings)> {
+        let start = Instant::now();
+        let content = read_input(config, self.parse_number(), input)?;
+
+        self.validate(&content)?;
+
+        let input = start.elapsed();
+
+        deadline::with_deadline(timeout, || self.solve_parsed(content, part, input))
+    }
+
+    fn validate(&self, content: &[u8]) -> Result<()> {
+        let content = std::str::from_utf8(content).map_err(|e| {
+            let before = &content[..e.valid_up_to()];
+            let line = before.iter().filter(|&&c| c == b'\n').count();
+            let column = before.len()
+                - before
+                    .iter()
+                    .rposition(|&c| c == b'\n')
+                    .map_or(0, |x| x + 1);
+
+            Error::Invalid {
+                line: Some(line + 1),
+                column: Some(column + 1),
+                reason: "not UTF-8".to_string(),
+            }
+        })?;
+
+        (self.validator)().check(content)
+    }
+
+    fn solve_parsed(
+        &self,
+        content: Vec<u8>,
+        part: Option<u8>,
+        input: Duration,
+    ) -> Result<(Answers, Timings)> {
+        let mut timings = Timings {
+            input,
+            ..Timings::default()
+        };
+
+        let start = Instant::now();
+        let parsed = (self.parse)(lines(content))?;
+
+        timings.parse = start.elapsed();
+        check_deadline()?;
+
+        let run = |n: u8, solve: fn(&dyn Any) -> Result<String>| {
+            if part.is_some_and(|p| p != n) {
+                return Ok((None, None));
+            }
+
+            let start = Instant::now();
+            let answer = solve(parsed.as_ref())?;
+            let duration = start.elapsed();
+
+            check_deadline()?;
+            Ok::<_, Error>((Some(answer), Some(duration)))
+        };
+        let (part1, duration1) = run(1, self.part1)?;
+        let (part2, duration2) = run(2, self.part2)?;
+
+        timings.part1 = duration1;
+        timings.part2 = duration2;
+
+        Ok((Answers { part1, part2 }, timings))
+    }
+
+    // the input is parsed again for every run
+    fn bench(&self, content: &[u8], options: &BenchOptions) -> Result<DayBench> {
+        let mut samples = [vec![], vec![], vec![]];
+
+        for run in 0..options.warmup + options.runs {
+            let lines = lines(content.to_vec());
+            let mut durations = [Duration::ZERO; 3];
+
+            let start = Instant::now();
+            let parsed = (self.parse)(lines)?;
+
+            durations[0] = start.elapsed();
+
+            for (duration, solve) in durations[1..].iter_mut().zip([self.part1, self.part2]) {
+                let start = Instant::now();
+
+                hint::black_box(solve(parsed.as_ref())?);
+                *duration = start.elapsed();
+            }
+
+            if run >= options.warmup {
+                for (samples, duration) in samples.iter_mut().zip(durations) {
+                    samples.push(duration);
+                }
+            }
+        }
+
+        Ok(DayBench {
+            day: self.parse_number(),
+            phases: samples.map(|samples| Stats::new(&samples)),
+        })
+    }
+
+    fn resolve(&self, config: &Config, input: Option<&Path>, part: Option<u8>) -> Result<Answers> {
+        self.solve(config, input, part, None)
+            .map(|(answers, _)| answers)
     }
 }
 
@@ -118,60 +359,694 @@ impl PartialOrd for Day {
     }
 }
 
-fn read_lines(day_number: u32) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let filename = format!("./inputs/{day_number:0>2}.txt");
-    let path = Path::new(&filename);
+// "-" reads the input from stdin, without a path the cached puzzle input is
+// used, it is downloaded first if needed
+fn read_input(config: &Config, day_number: u32, input: Option<&Path>) -> Result<Vec<u8>> {
+    read_input_from(io::stdin().lock(), config, day_number, input)
+}
 
-    if !path.exists() {
-        println!("downloading input for day {day_number}");
+// stdin is given so that the tests can stand in for it
+fn read_input_from(
+    mut stdin: impl Read,
+    config: &Config,
+    day_number: u32,
+    input: Option<&Path>,
+) -> Result<Vec<u8>> {
+    let mut content = vec![];
 
-	let session = env::var("AOC_SESSION").expect("AOC_SESSION not set");
-        let mut file = File::create(path)?;
-	let mut handle = Easy::new();
+    match input {
+        Some(path) if path == Path::new("-") => {
+            stdin.read_to_end(&mut content)?;
+        }
+        Some(path) => {
+            File::open(path)?.read_to_end(&mut content)?;
+        }
+        None => {
+            let path = config.input_path(day_number);
 
-	handle.cookie(&format!("session={session}"))?;
-	handle.url(&format!("https://adventofcode.com/2023/day/{day_number}/input"))?;
+            if !path.exists() {
+                eprintln!("downloading input for day {day_number}");
 
-	handle.write_function(
-	    move |data| {
-		Ok(file.write(data).unwrap())
-	    })?;
-	handle.perform()?;
+                download::download_input(config, &session::session(config)?, day_number)?;
+            }
+
+            File::open(path)?.read_to_end(&mut content)?;
+        }
     }
 
-    let file = File::open(path)?;
+    Ok(content)
+}
+
+// the input is fully in memory so that the parsing is not slowed down by reads
+fn lines(content: Vec<u8>) -> Input {
+    let reader: Box<dyn BufRead> = Box::new(Cursor::new(content));
 
-    Ok(io::BufReader::new(file).lines())
+    reader.lines()
 }
 
-fn resolve_all() {
+// every registered day, in order
+fn registered() -> Vec<&'static Day> {
     let mut days: Vec<&'static Day> = inventory::iter::<Day>.into_iter().collect();
 
     days.sort_unstable();
-
-    days.iter().for_each(|d| d.print());
+    days
 }
 
-fn resolve_one(day_number: u32) {
+fn find(day_number: u32) -> Result<&'static Day> {
     inventory::iter::<Day>
         .into_iter()
         .find(|d| d.parse_number() == day_number)
-        .unwrap()
-        .print();
+        .ok_or(Error::NotImplemented(day_number))
 }
 
-pub fn resolve(days: &[u32]) {
-    let start = Instant::now();
+// no selection means every registered day
+fn select(days: &[Selection]) -> Vec<(u32, Option<&'static Day>)> {
+    let implemented = registered()
+        .iter()
+        .map(|d| d.parse_number())
+        .collect::<Vec<_>>();
+
+    selection::expand(days, &implemented)
+        .into_iter()
+        .map(|day_number| (day_number, find(day_number).ok()))
+        .collect()
+}
 
-    if days.is_empty() {
-        resolve_all();
+// a day not implemented is reported and fails the command
+fn implemented(day_number: u32, day: Option<&'static Day>) -> Option<&'static Day> {
+    if day.is_none() {
+        println!("day{day_number:0>2}: not implemented");
+    }
+    day
+}
+
+// the CPU time of the calling thread, a day being solved on a single thread
+// it does not count the other days nor the idle threads of the pool
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: clock_gettime only fills time
+    if unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) } != 0 {
+        return None;
+    }
+
+    Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+// the records are in day order, they are printed as the days are solved
+// unless in parallel where they are only printed once every day is solved
+fn run(
+    config: &Config,
+    days: &[Selection],
+    input: Option<&Path>,
+    part: Option<u8>,
+    parallel: bool,
+    timeout: Option<Duration>,
+    print: bool,
+) -> Vec<Record> {
+    let days = select(days);
+    let run = |&(day, d): &(u32, Option<&'static Day>)| match d {
+        Some(d) => d.run(config, input, part, timeout),
+        None => Record {
+            day,
+            answers: Answers::default(),
+            timings: None,
+            cpu: None,
+            status: Status::Missing,
+        },
+    };
+
+    if parallel {
+        let records = days.par_iter().map(run).collect::<Vec<_>>();
+
+        if print {
+            records.iter().for_each(|r| println!("{}", r.human()));
+        }
+        records
     } else {
-        days.iter().for_each(|&d| resolve_one(d));
+        days.iter()
+            .map(|d| {
+                let record = run(d);
+
+                if print {
+                    println!("{}", record.human());
+                }
+                record
+            })
+            .collect()
     }
+}
 
+// input replaces the cached puzzle input, it only makes sense with a single day
+pub fn resolve(
+    config: &Config,
+    days: &[Selection],
+    input: Option<&Path>,
+    part: Option<u8>,
+    format: Format,
+    parallel: bool,
+    timeout: Option<Duration>,
+) -> bool {
+    let start = Instant::now();
+    let records = run(
+        config,
+        days,
+        input,
+        part,
+        parallel,
+        timeout,
+        format == Format::Human,
+    );
     let duration = start.elapsed();
+    // the sum over the days that were run, even when solved in parallel
+    let cpu = records.iter().filter_map(|r| r.cpu).reduce(|a, b| a + b);
+
+    if format == Format::Human {
+        let timings = records
+            .iter()
+            .filter_map(|r| Some((r.day, r.timings?)))
+            .collect::<Vec<_>>();
+
+        if !timings.is_empty() {
+            print!("\n{}", report::summary(&timings));
+        }
+
+        match cpu {
+            Some(cpu) => println!("All done in {duration:?} ({cpu:?} of CPU time)"),
+            None => println!("All done in {duration:?}"),
+        }
+    } else {
+        print!("{}", report::render(format, &records));
+    }
+
+    records.iter().all(|r| r.status == Status::Ok)
+}
+
+pub fn verify(config: &Config, days: &[Selection], part: Option<u8>) -> bool {
+    select(days)
+        .into_iter()
+        .filter(|&(n, d)| !implemented(n, d).is_some_and(|d| d.verify(config, part)))
+        .count()
+        == 0
+}
+
+pub fn record(config: &Config, days: &[Selection]) -> bool {
+    select(days)
+        .into_iter()
+        .filter(|&(n, d)| !implemented(n, d).is_some_and(|d| d.record(config)))
+        .count()
+        == 0
+}
+
+pub fn bench(config: &Config, days: &[Selection], options: &BenchOptions) -> bool {
+    let baseline = match options.baseline.as_deref().map(bench::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            println!("failed to load the baseline: {e}");
+            return false;
+        }
+    };
+    let mut benches = vec![];
+    let mut ok = true;
+
+    println!(
+        "{:6} {:6} {:>12} {:>12} {:>12} {:>12} {:>10}",
+        "", "phase", "min", "median", "mean", "stddev", "baseline"
+    );
+
+    for (day_number, day) in select(days) {
+        let Some(day) = implemented(day_number, day) else {
+            ok = false;
+            continue;
+        };
+        let result =
+            read_input(config, day_number, None).and_then(|content| day.bench(&content, options));
+
+        let bench = match result {
+            Ok(bench) => bench,
+            Err(e) => {
+                println!("day{day_number:0>2}: failed: {e}");
+                ok = false;
+                continue;
+            }
+        };
+        let changes = baseline.as_deref().and_then(|b| bench.compare(b));
+
+        for (i, (name, stats)) in bench::PHASES.iter().zip(bench.phases).enumerate() {
+            let change = match changes {
+                Some(changes) if changes[i] * 100.0 > options.threshold => {
+                    ok = false;
+                    format!("{:+.1}% REGRESSION", changes[i] * 100.0)
+                }
+                Some(changes) => format!("{:+.1}%", changes[i] * 100.0),
+                None => "-".to_string(),
+            };
+
+            println!(
+                "day{day_number:0>2}  {name:6} {:>12} {:>12} {:>12} {:>12} {change:>10}",
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.stddev),
+            );
+        }
+
+        benches.push(bench);
+    }
+
+    if let Some(path) = &options.save {
+        match bench::save(path, options.runs, &benches) {
+            Ok(()) => println!("baseline saved to {}", path.display()),
+            Err(e) => {
+                println!("failed to save the baseline: {e}");
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+// without a selection every day of the calendar is listed
+pub fn list(config: &Config, days: &[Selection]) -> bool {
+    let calendar = ["all".parse().unwrap()];
+    let days = if days.is_empty() { &calendar } else { days };
+    let mut ok = true;
+
+    println!("{:6} {:8} {:8} answers", "", "solver", "input");
+
+    for (day_number, day) in select(days) {
+        let answers = match Answers::load(&config.answers_dir, day_number) {
+            Ok(Answers { part1, part2 }) => match (part1, part2) {
+                (Some(_), Some(_)) => "part1 part2",
+                (Some(_), None) => "part1",
+                (None, Some(_)) => "part2",
+                (None, None) => "-",
+            }
+            .to_string(),
+            Err(e) => {
+                ok = false;
+                format!("failed: {e}")
+            }
+        };
+
+        println!(
+            "day{day_number:0>2}  {:8} {:8} {answers}",
+            if day.is_some() { "yes" } else { "-" },
+            if config.input_path(day_number).exists() {
+                "cached"
+            } else {
+                "-"
+            },
+        );
+    }
+
+    ok
+}
+
+pub fn validate(config: &Config, days: &[Selection], input: Option<&Path>) -> bool {
+    select(days)
+        .into_iter()
+        .filter(|&(day_number, day)| {
+            let Some(day) = implemented(day_number, day) else {
+                return true;
+            };
+
+            match read_input(config, day_number, input).and_then(|content| day.validate(&content)) {
+                Ok(()) => {
+                    println!("day{day_number:0>2}: valid");
+                    false
+                }
+                Err(e) => {
+                    println!("day{day_number:0>2}: {e}");
+                    true
+                }
+            }
+        })
+        .count()
+        == 0
+}
+
+pub fn submit(config: &Config, day_number: u32, part: u8) -> bool {
+    let result = find(day_number)
+        .and_then(|day| day.resolve(config, None, Some(part)))
+        .and_then(|answers| {
+            let answer = if part == 1 {
+                answers.part1
+            } else {
+                answers.part2
+            }
+            .unwrap_or_default();
+
+            println!("day{day_number:0>2}: submitting {answer} for part {part}");
+
+            submit::submit(
+                config,
+                &session::session(config)?,
+                day_number,
+                part,
+                &answer,
+            )
+        });
+
+    match result {
+        Ok(outcome) => {
+            println!("day{day_number:0>2}: {outcome}");
+            outcome == submit::Outcome::Right
+        }
+        Err(e) => {
+            println!("day{day_number:0>2}: failed: {e}");
+            false
+        }
+    }
+}
+
+pub fn puzzle(config: &Config, day_number: u32, offline: bool) -> bool {
+    match puzzle::fetch(config, day_number, offline) {
+        Ok(html) => {
+            println!("{}", puzzle::render(&html));
+            true
+        }
+        Err(e) => {
+            println!("day{day_number:0>2}: failed: {e}");
+            false
+        }
+    }
+}
+
+// the examples already there may have been fixed by hand, they are only
+// replaced when forced
+fn extract_examples(
+    config: &Config,
+    day_number: u32,
+    offline: bool,
+    force: bool,
+) -> Result<String> {
+    if !force && !examples::load(&config.examples_dir, day_number)?.is_empty() {
+        return Ok("examples already extracted, use --force to replace them".to_string());
+    }
+
+    let html = puzzle::fetch(config, day_number, offline)?;
+    let examples = examples::extract(&html);
+
+    if examples.is_empty() {
+        return Ok("no example found".to_string());
+    }
+
+    examples::save(&config.examples_dir, day_number, &examples)?;
+    Ok(format!(
+        "{} example(s) written to {}",
+        examples.len(),
+        config.examples_dir.display()
+    ))
+}
+
+pub fn examples(config: &Config, days: &[Selection], offline: bool, force: bool) -> bool {
+    let extract = |day_number| extract_examples(config, day_number, offline, force);
+
+    // the examples only need the puzzle, not the solver
+    let print = |&(day_number, _): &(u32, Option<&'static Day>)| match extract(day_number) {
+        Ok(message) => {
+            println!("day{day_number:0>2}: {message}");
+            true
+        }
+        Err(e) => {
+            println!("day{day_number:0>2}: failed: {e}");
+            false
+        }
+    };
+
+    select(days).iter().filter(|d| !print(d)).count() == 0
+}
+
+// runs every registered day on the examples of dir, only the parts with a
+// known answer are run and a day without example is a failure
+pub fn check_examples(dir: &Path) -> Vec<String> {
+    let mut failures = vec![];
+
+    for day in registered() {
+        let day_number = day.parse_number();
+        let examples = match examples::load(dir, day_number) {
+            Ok(examples) if examples.is_empty() => {
+                failures.push(format!(
+                    "day{day_number:0>2}: no example in {}, run the examples command",
+                    dir.display()
+                ));
+                continue;
+            }
+            Ok(examples) => examples,
+            Err(e) => {
+                failures.push(format!("day{day_number:0>2}: {e}"));
+                continue;
+            }
+        };
+
+        for (path, answers) in examples {
+            let name = path.display();
+            let part = match (&answers.part1, &answers.part2) {
+                (Some(_), None) => Some(1),
+                (None, Some(_)) => Some(2),
+                _ => None,
+            };
+
+            match day.resolve(&Config::default(), Some(&path), part) {
+                Ok(solved) => {
+                    let (part1, part2) = answers.check(&solved);
+
+                    if matches!(part1, Check::Fail { .. }) {
+                        failures.push(format!("{name}: part1: {part1}"));
+                    }
+                    if matches!(part2, Check::Fail { .. }) {
+                        failures.push(format!("{name}: part2: {part2}"));
+                    }
+                }
+                Err(e) => failures.push(format!("{name}: failed: {e}")),
+            }
+        }
+    }
+
+    failures
+}
+
+// the day has no test of its own, its examples are checked with the others
+// once extracted, which can be done along
+pub fn new_day(config: &Config, day_number: u32, dir: &Path, with_examples: bool) -> bool {
+    let result = (|| -> Result<_> {
+        let path = scaffold::create(dir, day_number, &scaffold::generate(day_number))?;
+
+        if with_examples {
+            println!(
+                "day{day_number:0>2}: {}",
+                extract_examples(config, day_number, false, false)?
+            );
+        }
+
+        Ok(path)
+    })();
+
+    match result {
+        Ok(path) => {
+            println!("day{day_number:0>2}: created {}", path.display());
+            true
+        }
+        Err(e) => {
+            println!("day{day_number:0>2}: failed: {e}");
+            false
+        }
+    }
+}
+
+pub fn login_check(config: &Config) -> bool {
+    match session::session(config).and_then(|session| session::check(config, &session)) {
+        Ok(user) => {
+            println!("logged in to {} as {user}", config.base_url);
+            true
+        }
+        Err(e) => {
+            println!("login check failed: {e}");
+            false
+        }
+    }
+}
+
+#[test]
+fn check_parallel_order() {
+    use crate::mock::{config, temp_dir};
+
+    let dir = temp_dir("parallel");
+    let config = config("http://localhost:0", &dir);
+    // day 1 is the test day of mock, the others are not implemented
+    let days = ["3".parse().unwrap(), "1-2".parse().unwrap()];
+
+    std::fs::write(config.input_path(1), "2\n3\n4\n").unwrap();
+
+    let strip = |records: Vec<Record>| {
+        records
+            .into_iter()
+            .map(|r| (r.day, r.answers, r.status))
+            .collect::<Vec<_>>()
+    };
+    // in the order the days are given
+    let expected = [
+        (3, Answers::default(), Status::Missing),
+        (
+            1,
+            Answers {
+                part1: Some("9".to_string()),
+                part2: Some("24".to_string()),
+            },
+            Status::Ok,
+        ),
+        (2, Answers::default(), Status::Missing),
+    ];
+
+    for parallel in [true, false] {
+        let records = run(&config, &days, None, None, parallel, None, false);
+
+        // only the days run have a CPU time
+        assert_eq!(
+            records.iter().map(|r| r.cpu.is_some()).collect::<Vec<_>>(),
+            [false, cfg!(unix), false]
+        );
+        assert_eq!(strip(records), expected);
+    }
+}
+
+#[test]
+fn check_read_input() {
+    use crate::mock::{config, temp_dir, MockServer};
+
+    let dir = temp_dir("read-input");
+    let server = MockServer::start(vec![(200, "7\n")]);
+    let config = Config {
+        session: Some("cookie".to_string()),
+        ..config(&server.url, &dir)
+    };
+    let read =
+        |input: Option<&str>| read_input_from(&b"1\n2\n"[..], &config, 1, input.map(Path::new));
+
+    std::fs::write(dir.join("other.txt"), "3\n").unwrap();
+
+    assert_eq!(read(Some("-")).unwrap(), b"1\n2\n");
+    assert_eq!(
+        read(Some(dir.join("other.txt").to_str().unwrap())).unwrap(),
+        b"3\n"
+    );
+    assert!(matches!(
+        read(Some(dir.join("nothing.txt").to_str().unwrap())),
+        Err(Error::Io(_))
+    ));
+    // downloaded once, then read from the input directory
+    assert_eq!(read(None).unwrap(), b"7\n");
+    assert_eq!(read(None).unwrap(), b"7\n");
+    // a run without the input file is served from the HTTP cache
+    std::fs::remove_file(config.input_path(1)).unwrap();
+    assert_eq!(read(None).unwrap(), b"7\n");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn check_part_selection() {
+    let day = find(1).unwrap();
+    // part 2 of the test day fails on a negative number
+    let solve = |part| day.solve_parsed(b"2\n-3\n".to_vec(), part, Duration::ZERO);
+    let (answers, timings) = solve(Some(1)).unwrap();
+
+    assert_eq!(
+        answers,
+        Answers {
+            part1: Some("-1".to_string()),
+            part2: None,
+        }
+    );
+    assert!(timings.part1.is_some());
+    assert_eq!(timings.part2, None);
+    assert!(matches!(solve(Some(2)), Err(Error::Unsolvable(_))));
+    assert!(matches!(solve(None), Err(Error::Unsolvable(_))));
+}
+
+#[test]
+fn check_verify_record() {
+    use crate::mock::{config, temp_dir};
+
+    let dir = temp_dir("verify-record");
+    let config = Config {
+        answers_dir: dir.join("answers"),
+        ..config("http://localhost:0", &dir)
+    };
+    let answers = config.answers_dir.join("01.txt");
+    let day = find(1).unwrap();
+
+    std::fs::write(config.input_path(1), "2\n3\n4\n").unwrap();
+
+    // nothing known yet, nothing fails
+    assert!(day.verify(&config, None));
+    assert!(day.record(&config));
+    assert_eq!(
+        Answers::load(&config.answers_dir, 1).unwrap(),
+        Answers {
+            part1: Some("9".to_string()),
+            part2: Some("24".to_string()),
+        }
+    );
+    assert!(day.verify(&config, None));
+
+    std::fs::write(&answers, "9\n25\n").unwrap();
+
+    assert!(!day.verify(&config, None));
+    assert!(!day.verify(&config, Some(2)));
+    assert!(day.verify(&config, Some(1)));
+
+    std::fs::write(config.input_path(1), "x\n").unwrap();
+
+    assert!(!day.verify(&config, None));
+    assert!(!day.record(&config));
+    assert_eq!(std::fs::read_to_string(&answers).unwrap(), "9\n25\n");
+}
+
+#[test]
+fn check_examples_runner() {
+    use crate::mock::temp_dir;
+
+    let dir = temp_dir("check-examples");
+    let example = |input: &str, part1: &str| examples::Example {
+        input: input.to_string(),
+        answers: Answers {
+            part1: Some(part1.to_string()),
+            part2: None,
+        },
+    };
+
+    let failures = check_examples(&dir);
+
+    assert_eq!(failures.len(), 1);
+    assert!(failures[0].contains("day01: no example in "));
+
+    // part 2 of the test day would fail on the third one but is not run
+    examples::save(
+        &dir,
+        1,
+        &[
+            example("2\n3\n", "5"),
+            example("4\n", "5"),
+            example("-1\n", "-1"),
+        ],
+    )
+    .unwrap();
+
+    let failures = check_examples(&dir);
 
-    println!("All done in {duration:?}");
+    assert_eq!(failures.len(), 1);
+    assert!(failures[0].contains("01-2.txt: part1: "));
 }
 
 inventory::collect!(Day);
@@ -1,18 +1,239 @@
-use clap::Parser;
+use advent_2023::Selection;
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 mod days;
 
+const DAYS_HELP: &str =
+    "days as numbers, ranges (1-10, ..5, 20..), all, implemented or missing [default: implemented]";
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, help = "limit the number of threads used by rayon")]
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "limit the number of threads used by rayon"
+    )]
     nthreads: Option<usize>,
-    #[arg(trailing_var_arg = true)]
-    days: Vec<u32>,
+    #[arg(
+        short,
+        long,
+        help = "read the input of the given day from a file instead of the cache (- for stdin)"
+    )]
+    input: Option<PathBuf>,
+    #[arg(
+        short,
+        long,
+        value_parser = clap::value_parser!(u8).range(1..=2),
+        help = "only run the given part"
+    )]
+    part: Option<u8>,
+    #[arg(
+        long,
+        default_value = "human",
+        help = "output of the results: human, json, csv or markdown (json and csv durations in nanoseconds)"
+    )]
+    format: advent_2023::Format,
+    #[arg(long, help = "solve the days concurrently on the rayon pool")]
+    parallel: bool,
+    #[arg(
+        long,
+        value_parser = advent_2023::parse_duration,
+        help = "give up on a day after a duration (ms, s or m)"
+    )]
+    timeout: Option<Duration>,
+    #[arg(
+        long,
+        global = true,
+        help = "settings file (default: $AOC_CONFIG or ./aoc.toml)"
+    )]
+    config: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "adventofcode.com or a mirror [env: AOC_BASE_URL]"
+    )]
+    base_url: Option<String>,
+    #[arg(long, global = true, help = "puzzle year [env: AOC_YEAR]")]
+    year: Option<u32>,
+    #[arg(
+        long,
+        global = true,
+        help = "where inputs are cached [env: AOC_INPUT_DIR]"
+    )]
+    input_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "where known answers are stored [env: AOC_ANSWERS_DIR]"
+    )]
+    answers_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "where examples are stored [env: AOC_EXAMPLES_DIR]"
+    )]
+    examples_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "session cookie (default: $AOC_SESSION or ~/.config/aoc/session)"
+    )]
+    session: Option<String>,
+    #[arg(trailing_var_arg = true, help = DAYS_HELP)]
+    days: Vec<Selection>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "show the implemented days, the cached inputs and the known answers")]
+    List {
+        #[arg(help = "days to list, all of them by default")]
+        days: Vec<Selection>,
+    },
+    #[command(about = "check that the inputs have the shape the days expect")]
+    Validate {
+        #[arg(help = DAYS_HELP)]
+        days: Vec<Selection>,
+        #[arg(
+            short,
+            long,
+            help = "validate a file instead of the cached input of a single day (- for stdin)"
+        )]
+        input: Option<PathBuf>,
+    },
+    #[command(about = "check the answers against the known-good ones")]
+    Verify {
+        #[arg(help = DAYS_HELP)]
+        days: Vec<Selection>,
+        #[arg(
+            short,
+            long,
+            value_parser = clap::value_parser!(u8).range(1..=2),
+            help = "only check the given part"
+        )]
+        part: Option<u8>,
+    },
+    #[command(about = "store the current answers as the known-good ones")]
+    Record {
+        #[arg(help = DAYS_HELP)]
+        days: Vec<Selection>,
+    },
+    #[command(about = "run the solvers repeatedly and show statistics of their timings")]
+    Bench {
+        #[arg(help = DAYS_HELP)]
+        days: Vec<Selection>,
+        #[arg(long, default_value_t = 3, help = "runs discarded before measuring")]
+        warmup: usize,
+        #[arg(
+            long,
+            default_value_t = 10,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "measured runs"
+        )]
+        runs: u64,
+        #[arg(long, help = "save the statistics as a baseline")]
+        save: Option<PathBuf>,
+        #[arg(long, help = "compare the medians with a saved baseline")]
+        baseline: Option<PathBuf>,
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "slowdown in percent reported as a regression"
+        )]
+        threshold: f64,
+    },
+    #[command(about = "submit the answer of one part to adventofcode.com")]
+    Submit {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    #[command(about = "show the puzzle description, part 2 included once unlocked")]
+    Puzzle {
+        day: u32,
+        #[arg(long, help = "only use the cached page")]
+        offline: bool,
+    },
+    #[command(about = "extract the examples and their answers from the puzzle pages")]
+    Examples {
+        #[arg(help = DAYS_HELP)]
+        days: Vec<Selection>,
+        #[arg(long, help = "only use the cached pages")]
+        offline: bool,
+        #[arg(long, help = "replace the examples already extracted")]
+        force: bool,
+    },
+    #[command(about = "generate the source file of a new day")]
+    New {
+        day: u32,
+        #[arg(long, default_value = "src/days", help = "where the days are")]
+        dir: PathBuf,
+        #[arg(long, help = "extract the examples of the day too")]
+        examples: bool,
+    },
+    #[command(about = "manage the adventofcode.com session")]
+    Login {
+        #[command(subcommand)]
+        action: Login,
+    },
+}
+
+#[derive(Subcommand)]
+enum Login {
+    #[command(about = "check the session token and show the account name")]
+    Check,
 }
 
-fn main() {
+fn single_day(days: &[Selection]) -> bool {
+    matches!(days, [Selection::Days(r)] if r.start() == r.end())
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
 
+    if args.input.is_some() && !single_day(&args.days) {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--input requires exactly one day",
+            )
+            .exit();
+    }
+
+    let mut config = match advent_2023::Config::load(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(base_url) = args.base_url {
+        config.base_url = base_url.trim_end_matches('/').to_string();
+    }
+    if let Some(year) = args.year {
+        config.year = year;
+    }
+    if let Some(input_dir) = args.input_dir {
+        config.input_dir = input_dir;
+    }
+    if let Some(answers_dir) = args.answers_dir {
+        config.answers_dir = answers_dir;
+    }
+    if let Some(examples_dir) = args.examples_dir {
+        config.examples_dir = examples_dir;
+    }
+
+    if let Some(session) = args.session {
+        config.session = Some(session);
+    }
+
     if let Some(nthreads) = args.nthreads {
         rayon::ThreadPoolBuilder::new()
             .num_threads(nthreads)
@@ -20,5 +241,66 @@ fn main() {
             .unwrap();
     }
 
-    advent_2023::resolve(&args.days);
+    let ok = match args.command {
+        Some(Command::Validate { days, input }) => {
+            if input.is_some() && !single_day(&days) {
+                Args::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "--input requires exactly one day",
+                    )
+                    .exit();
+            }
+            advent_2023::validate(&config, &days, input.as_deref())
+        }
+        Some(Command::List { days }) => advent_2023::list(&config, &days),
+        Some(Command::Verify { days, part }) => advent_2023::verify(&config, &days, part),
+        Some(Command::Record { days }) => advent_2023::record(&config, &days),
+        Some(Command::Bench {
+            days,
+            warmup,
+            runs,
+            save,
+            baseline,
+            threshold,
+        }) => advent_2023::bench(
+            &config,
+            &days,
+            &advent_2023::BenchOptions {
+                warmup,
+                runs: runs as usize,
+                save,
+                baseline,
+                threshold,
+            },
+        ),
+        Some(Command::Submit { day, part }) => advent_2023::submit(&config, day, part),
+        Some(Command::Puzzle { day, offline }) => advent_2023::puzzle(&config, day, offline),
+        Some(Command::Examples {
+            days,
+            offline,
+            force,
+        }) => advent_2023::examples(&config, &days, offline, force),
+        Some(Command::New { day, dir, examples }) => {
+            advent_2023::new_day(&config, day, &dir, examples)
+        }
+        Some(Command::Login {
+            action: Login::Check,
+        }) => advent_2023::login_check(&config),
+        None => advent_2023::resolve(
+            &config,
+            &args.days,
+            args.input.as_deref(),
+            args.part,
+            args.format,
+            args.parallel,
+            args.timeout,
+        ),
+    };
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
@@ -0,0 +1,123 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+// a local stand-in for adventofcode.com answering each connection with the
+// next canned (status, body) response and recording the raw requests
+pub struct MockServer {
+    pub url: String,
+    handle: JoinHandle<Vec<String>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<(u32, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+
+                loop {
+                    let mut line = String::new();
+
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut content = vec![0; length];
+
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8_lossy(&content));
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+
+            requests
+        });
+
+        MockServer { url, handle }
+    }
+
+    pub fn requests(self) -> Vec<String> {
+        self.handle.join().unwrap()
+    }
+}
+
+// a fresh directory for tests touching the filesystem
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("advent_2023-{name}-{}", std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// everything stored under dir and no throttling
+pub fn config(url: &str, dir: &std::path::Path) -> crate::Config {
+    crate::Config {
+        base_url: url.to_string(),
+        input_dir: dir.to_path_buf(),
+        answers_dir: dir.to_path_buf(),
+        examples_dir: dir.join("puzzles"),
+        cache_dir: Some(dir.join(".cache")),
+        request_interval: std::time::Duration::ZERO,
+        ..crate::Config::default()
+    }
+}
+
+// a day 1 for the tests of the runner, part 1 is the sum of the numbers and
+// part 2 their product, which fails on a negative number
+pub struct Sum;
+
+impl crate::Solution for Sum {
+    type Parsed = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<T>(lines: std::io::Lines<T>) -> crate::Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        lines
+            .enumerate()
+            .map(|(index, line)| {
+                line?
+                    .parse()
+                    .map_err(|_| crate::Error::parse(index, "expected a number"))
+            })
+            .collect()
+    }
+
+    fn part1(numbers: &Self::Parsed) -> crate::Result<i64> {
+        Ok(numbers.iter().sum())
+    }
+
+    fn part2(numbers: &Self::Parsed) -> crate::Result<i64> {
+        if numbers.iter().any(|&n| n < 0) {
+            return Err(crate::Error::unsolvable("negative number"));
+        }
+
+        Ok(numbers.iter().product())
+    }
+}
+
+inventory::submit! { crate::Day::new::<Sum>("day01.rs") }
@@ -0,0 +1,241 @@
+use crate::error::DownloadError;
+use crate::http::{self, Response};
+use crate::{session, Config, Result};
+
+#[derive(Debug, PartialEq)]
+pub enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(String),
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// just enough of HTML for the puzzle pages: tags, their raw attributes and
+// the decoded text in between, comments are dropped
+pub fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').unwrap_or(rest.len() - 1);
+            let tag = rest[1..end].trim_end_matches('/');
+            let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+
+            match name.strip_prefix('/') {
+                Some(name) => tokens.push(Token::Close(name)),
+                None => tokens.push(Token::Open(name, attributes)),
+            }
+
+            rest = &rest[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+
+            tokens.push(Token::Text(decode(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+pub fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = start + attributes[start..].find('"')?;
+
+    Some(&attributes[start..end])
+}
+
+// the contents of each <article>, part 2 being the second one once unlocked
+pub fn articles(html: &str) -> Vec<&str> {
+    html.split("<article")
+        .skip(1)
+        .filter_map(|a| {
+            let start = a.find('>')? + 1;
+            let end = a.find("</article>")?;
+
+            Some(&a[start..end])
+        })
+        .collect()
+}
+
+fn render_article(article: &str) -> String {
+    let mut md = String::new();
+    let mut in_pre = false;
+    let mut links = vec![];
+
+    for token in tokens(article) {
+        match token {
+            Token::Open("h2", _) => md.push_str("## "),
+            Token::Open("pre", _) => {
+                md.push_str("```\n");
+                in_pre = true;
+            }
+            Token::Close("pre") => {
+                if !md.ends_with('\n') {
+                    md.push('\n');
+                }
+                md.push_str("```\n\n");
+                in_pre = false;
+            }
+            Token::Open("code", _) | Token::Close("code") if !in_pre => md.push('`'),
+            Token::Open("em", _) | Token::Close("em") if !in_pre => md.push('*'),
+            Token::Open("a", attributes) => {
+                md.push('[');
+                links.push(attribute(attributes, "href").unwrap_or("").to_string());
+            }
+            Token::Close("a") => md.push_str(&format!("]({})", links.pop().unwrap_or_default())),
+            Token::Open("li", _) => md.push_str("- "),
+            Token::Close("li") => md.push('\n'),
+            Token::Close("h2") | Token::Close("p") | Token::Close("ul") => md.push_str("\n\n"),
+            Token::Text(text) if in_pre => md.push_str(&text),
+            Token::Text(text) => md.push_str(&text.replace('\n', " ").replace("  ", " ")),
+            _ => {}
+        }
+    }
+
+    md
+}
+
+pub fn render(html: &str) -> String {
+    let md = articles(html)
+        .into_iter()
+        .map(render_article)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut lines: Vec<&str> = vec![];
+    let mut in_pre = false;
+
+    // the indentation of the HTML source leaks everywhere but in code blocks
+    for line in md.lines() {
+        let line = if in_pre { line } else { line.trim() };
+
+        if line == "```" {
+            in_pre = !in_pre;
+        }
+
+        if !(line.is_empty() && lines.last().is_some_and(|l| l.is_empty())) {
+            lines.push(line);
+        }
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+// the page goes through the HTTP cache, it is fetched again as long as part 2
+// is not unlocked, unless offline, the page already cached being kept when
+// that fails
+pub fn fetch(config: &Config, day_number: u32, offline: bool) -> Result<String> {
+    let session = session::session(config).unwrap_or_default();
+    let url = config.day_url(day_number);
+    let cached = http::cached(config, &url, &session)?.map(|response| response.text());
+
+    match &cached {
+        Some(html) if offline || articles(html).len() > 1 => return Ok(html.clone()),
+        None if offline => return Err(DownloadError::Offline.into()),
+        _ => {}
+    }
+
+    let check = |response: &Response| -> Result<()> {
+        match response.status {
+            200 => Ok(()),
+            404 => Err(DownloadError::NotAvailable.into()),
+            status => Err(DownloadError::Http {
+                url: url.clone(),
+                status,
+            }
+            .into()),
+        }
+    };
+
+    match http::refresh(config, &url, &session, check) {
+        Ok(response) => Ok(response.text()),
+        Err(e) => cached.ok_or(e),
+    }
+}
+
+#[test]
+fn check_tokens() {
+    assert_eq!(
+        tokens("<p>a &lt;b&gt;<!-- c --><a href=\"x\">d</a><br/></p>"),
+        [
+            Token::Open("p", ""),
+            Token::Text("a <b>".to_string()),
+            Token::Open("a", "href=\"x\""),
+            Token::Text("d".to_string()),
+            Token::Close("a"),
+            Token::Open("br", ""),
+            Token::Close("p"),
+        ]
+    );
+}
+
+#[test]
+fn check_render() {
+    const PUZZLE: &str = include_str!("../fixtures/puzzle.html");
+
+    assert_eq!(articles(PUZZLE).len(), 2);
+    assert_eq!(
+        render(PUZZLE),
+        "## --- Day 1: Sum of Pairs ---
+
+The elves hand you a list of *number pairs*, one pair per line. For each line, add the *first* and the *last* number.
+
+For example:
+
+```
+1 2
+3 4
+5 <6>
+```
+
+In this example, the sums are `3`, `7` and `11`. Adding these together produces `*21*`.
+
+Consider your [puzzle input](1/input). *What is the sum of all pairs?*
+
+## --- Part Two ---
+
+The elves now want the *product* of each pair:
+
+- `1 2` gives `2`;
+- `3 4` gives `12`.
+
+With the example above, the sum of the products is `*44*`.
+
+*What is the sum of the products?*"
+    );
+}
+
+#[test]
+fn check_fetch() {
+    use crate::mock::{config, temp_dir, MockServer};
+
+    const PART1: &str = "<main><article><h2>--- Day 2 ---</h2></article></main>";
+    const BOTH: &str =
+        "<main><article><h2>--- Day 2 ---</h2></article><article><h2>--- Part Two ---</h2></article></main>";
+
+    let dir = temp_dir("puzzle");
+    let server = MockServer::start(vec![(200, PART1), (500, ""), (200, BOTH)]);
+    let config = config(&server.url, &dir);
+
+    assert!(fetch(&config, 2, true).is_err());
+    assert_eq!(fetch(&config, 2, false).unwrap(), PART1);
+    assert_eq!(fetch(&config, 2, true).unwrap(), PART1);
+    // part 2 not in the cache yet, the cached page outlives a failed refresh
+    assert_eq!(fetch(&config, 2, false).unwrap(), PART1);
+    assert_eq!(fetch(&config, 2, true).unwrap(), PART1);
+    assert_eq!(fetch(&config, 2, false).unwrap(), BOTH);
+    // from the cache only
+    assert_eq!(fetch(&config, 2, false).unwrap(), BOTH);
+    assert_eq!(server.requests().len(), 3);
+    assert!(!dir.join("02.html").exists());
+}
@@ -0,0 +1,378 @@
+use crate::answers::Answers;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Human,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format {s}, expected human, json, csv or markdown"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Failed(String),
+    TimedOut(Duration),
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Failed(_) => write!(f, "failed"),
+            Status::TimedOut(_) => write!(f, "timeout"),
+            Status::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+// the outcome of a day, the timings are only known when it did not fail, the
+// CPU time of the thread solving it whenever it was run
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub answers: Answers,
+    pub timings: Option<Timings>,
+    pub cpu: Option<Duration>,
+    pub status: Status,
+}
+
+// the input acquisition covers the download and the read of the whole
+// input, it is not part of the solve time
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub input: Duration,
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+// a part not run on a day does not hide the ones run on the others
+fn add(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        _ => a.or(b),
+    }
+}
+
+impl Timings {
+    pub fn solve(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+
+    fn add(&self, other: &Timings) -> Timings {
+        Timings {
+            input: self.input + other.input,
+            parse: self.parse + other.parse,
+            part1: add(self.part1, other.part1),
+            part2: add(self.part2, other.part2),
+        }
+    }
+
+    fn row(&self, name: &str) -> String {
+        let duration = |d: Option<Duration>| d.map_or("-".to_string(), |d| format!("{d:.2?}"));
+
+        format!(
+            "{name:6} {:>12} {:>12} {:>12} {:>12} {:>12}\n",
+            duration(Some(self.input)),
+            duration(Some(self.parse)),
+            duration(self.part1),
+            duration(self.part2),
+            duration(Some(self.solve())),
+        )
+    }
+}
+
+pub fn summary(timings: &[(u32, Timings)]) -> String {
+    let mut table = format!(
+        "{:6} {:>12} {:>12} {:>12} {:>12} {:>12}\n",
+        "", "input", "parse", "part1", "part2", "solve"
+    );
+    let mut total = Timings::default();
+
+    for (day_number, t) in timings {
+        table.push_str(&t.row(&format!("day{day_number:0>2}")));
+        total = total.add(t);
+    }
+
+    table.push_str(&total.row("total"));
+    table
+}
+
+impl Record {
+    pub fn human(&self) -> String {
+        let day_number = self.day;
+
+        match (&self.status, &self.timings) {
+            (Status::Failed(e), _) => format!("day{day_number:0>2}: failed: {e}"),
+            (Status::Missing, _) => format!("day{day_number:0>2}: not implemented"),
+            (Status::TimedOut(timeout), _) => {
+                format!("day{day_number:0>2}: timed out after {timeout:?}")
+            }
+            (Status::Ok, timings) => format!(
+                "day{day_number:0>2}: part1: {:20} part2: {:20} in {:?}",
+                self.answers.part1.as_deref().unwrap_or("-"),
+                self.answers.part2.as_deref().unwrap_or("-"),
+                timings.unwrap_or_default().solve()
+            ),
+        }
+    }
+
+    fn to_json(&self) -> JsonRecord<'_> {
+        let ns = |d: Duration| d.as_nanos() as u64;
+        let t = self.timings;
+
+        JsonRecord {
+            day: self.day,
+            answer1: self.answers.part1.as_deref(),
+            answer2: self.answers.part2.as_deref(),
+            input: t.map(|t| ns(t.input)),
+            parse: t.map(|t| ns(t.parse)),
+            time1: t.and_then(|t| t.part1).map(ns),
+            time2: t.and_then(|t| t.part2).map(ns),
+            solve: t.map(|t| ns(t.solve())),
+            status: self.status.to_string(),
+            error: match &self.status {
+                Status::Failed(e) => Some(e),
+                _ => None,
+            },
+            timeout: match self.status {
+                Status::TimedOut(timeout) => Some(ns(timeout)),
+                _ => None,
+            },
+        }
+    }
+
+    // the answers and the status, then the durations as given
+    fn fields(&self, duration: impl Fn(Option<Duration>) -> String) -> Vec<String> {
+        let t = self.timings;
+
+        vec![
+            format!("{}", self.day),
+            self.answers.part1.clone().unwrap_or_default(),
+            self.answers.part2.clone().unwrap_or_default(),
+            duration(t.map(|t| t.input)),
+            duration(t.map(|t| t.parse)),
+            duration(t.and_then(|t| t.part1)),
+            duration(t.and_then(|t| t.part2)),
+            duration(t.map(|t| t.solve())),
+            match &self.status {
+                Status::Failed(e) => format!("{}: {e}", self.status),
+                status => status.to_string(),
+            },
+        ]
+    }
+}
+
+// the columns of the CSV and Markdown tables, the error or the timeout, in
+// nanoseconds, being added when there is one
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    day: u32,
+    answer1: Option<&'a str>,
+    answer2: Option<&'a str>,
+    input: Option<u64>,
+    parse: Option<u64>,
+    time1: Option<u64>,
+    time2: Option<u64>,
+    solve: Option<u64>,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+}
+
+// the same schema in every format, the durations are in nanoseconds in JSON
+// and CSV, and human readable in Markdown
+const COLUMNS: [&str; 9] = [
+    "day", "answer1", "answer2", "input", "parse", "time1", "time2", "solve", "status",
+];
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// the machine-readable renderings of the records, the human one is printed
+// day by day as they are solved
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Human => records.iter().map(|r| r.human() + "\n").collect(),
+        Format::Json => {
+            let records = records.iter().map(Record::to_json).collect::<Vec<_>>();
+
+            // only strings and numbers, it cannot fail
+            serde_json::to_string(&records).unwrap() + "\n"
+        }
+        Format::Csv => {
+            let ns = |d: Option<Duration>| d.map_or(String::new(), |d| d.as_nanos().to_string());
+            let mut csv = COLUMNS.join(",") + "\n";
+
+            for record in records {
+                let fields = record.fields(ns);
+
+                csv.push_str(
+                    &fields
+                        .iter()
+                        .map(|f| csv_field(f))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                csv.push('\n');
+            }
+            csv
+        }
+        Format::Markdown => {
+            let duration = |d: Option<Duration>| d.map_or("-".to_string(), |d| format!("{d:.2?}"));
+            let mut table = format!("| {} |\n", COLUMNS.join(" | "));
+
+            table.push_str(&format!("|{}\n", "---|".repeat(COLUMNS.len())));
+            for record in records {
+                let fields = record
+                    .fields(duration)
+                    .iter()
+                    .map(|f| f.replace('|', "\\|").replace('\n', " "))
+                    .collect::<Vec<_>>();
+
+                table.push_str(&format!("| {} |\n", fields.join(" | ")));
+            }
+            table
+        }
+    }
+}
+
+#[test]
+fn check_summary() {
+    let ms = Duration::from_millis;
+    let timings = [
+        (
+            1,
+            Timings {
+                input: ms(1),
+                parse: ms(2),
+                part1: Some(ms(3)),
+                part2: None,
+            },
+        ),
+        (
+            9,
+            Timings {
+                input: ms(10),
+                parse: ms(20),
+                part1: Some(ms(30)),
+                part2: None,
+            },
+        ),
+    ];
+
+    assert_eq!(
+        summary(&timings),
+        "              input        parse        part1        part2        solve
+day01        1.00ms       2.00ms       3.00ms            -       5.00ms
+day09       10.00ms      20.00ms      30.00ms            -      50.00ms
+total       11.00ms      22.00ms      33.00ms            -      55.00ms
+"
+    );
+}
+
+#[test]
+fn check_render() {
+    let ms = Duration::from_millis;
+    let records = [
+        Record {
+            day: 9,
+            answers: Answers {
+                part1: Some("114".to_string()),
+                part2: None,
+            },
+            timings: Some(Timings {
+                input: ms(1),
+                parse: ms(2),
+                part1: Some(ms(3)),
+                part2: None,
+            }),
+            cpu: Some(ms(4)),
+            status: Status::Ok,
+        },
+        Record {
+            day: 10,
+            answers: Answers::default(),
+            timings: None,
+            cpu: None,
+            status: Status::Failed("no start, no loop".to_string()),
+        },
+    ];
+
+    assert_eq!(
+        render(Format::Json, &records),
+        r#"[{"day":9,"answer1":"114","answer2":null,"input":1000000,"parse":2000000,"time1":3000000,"time2":null,"solve":5000000,"status":"ok"},{"day":10,"answer1":null,"answer2":null,"input":null,"parse":null,"time1":null,"time2":null,"solve":null,"status":"failed","error":"no start, no loop"}]
+"#
+    );
+    assert_eq!(
+        render(Format::Csv, &records),
+        "day,answer1,answer2,input,parse,time1,time2,solve,status
+9,114,,1000000,2000000,3000000,,5000000,ok
+10,,,,,,,,\"failed: no start, no loop\"
+"
+    );
+    assert_eq!(
+        render(Format::Markdown, &records),
+        "| day | answer1 | answer2 | input | parse | time1 | time2 | solve | status |
+|---|---|---|---|---|---|---|---|---|
+| 9 | 114 |  | 1.00ms | 2.00ms | 3.00ms | - | 5.00ms | ok |
+| 10 |  |  | - | - | - | - | - | failed: no start, no loop |
+"
+    );
+    assert_eq!(
+        render(Format::Human, &records[1..]),
+        "day10: failed: no start, no loop\n"
+    );
+
+    let timed_out = [Record {
+        day: 17,
+        answers: Answers::default(),
+        timings: None,
+        cpu: None,
+        status: Status::TimedOut(ms(500)),
+    }];
+
+    assert_eq!(
+        render(Format::Json, &timed_out),
+        r#"[{"day":17,"answer1":null,"answer2":null,"input":null,"parse":null,"time1":null,"time2":null,"solve":null,"status":"timeout","timeout":500000000}]
+"#
+    );
+    assert!(render(Format::Csv, &timed_out).ends_with("\n17,,,,,,,,timeout\n"));
+    assert!(render(Format::Markdown, &timed_out).ends_with("| - | timeout |\n"));
+    assert_eq!(
+        render(Format::Human, &timed_out),
+        "day17: timed out after 500ms\n"
+    );
+    assert_eq!("csv".parse(), Ok(Format::Csv));
+    assert!("xml".parse::<Format>().is_err());
+}
@@ -0,0 +1,52 @@
+use crate::Result;
+use std::fs::OpenOptions;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+// the examples are not in the day, they are checked from the examples
+// directory
+pub fn generate(day_number: u32) -> String {
+    TEMPLATE.replace("{{DAY}}", &format!("{day_number:0>2}"))
+}
+
+// an existing day is never overwritten
+pub fn create(dir: &Path, day_number: u32, content: &str) -> Result<PathBuf> {
+    let path = dir.join(format!("day{day_number:0>2}.rs"));
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| match e.kind() {
+            ErrorKind::AlreadyExists => {
+                io::Error::new(e.kind(), format!("{} already exists", path.display()))
+            }
+            _ => e,
+        })?;
+
+    file.write_all(content.as_bytes())?;
+
+    Ok(path)
+}
+
+#[test]
+fn check_generate() {
+    let day = generate(9);
+
+    assert!(day.contains("impl Solution for Day09 {"));
+    assert!(!day.contains("{{"));
+    assert!(!day.contains("const TEST"));
+}
+
+#[test]
+fn check_create() {
+    let dir = crate::mock::temp_dir("scaffold");
+
+    assert_eq!(create(&dir, 3, "day").unwrap(), dir.join("day03.rs"));
+    assert!(create(&dir, 3, "other").is_err());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("day03.rs")).unwrap(),
+        "day"
+    );
+}
@@ -0,0 +1,272 @@
+use crate::{check_deadline, Result};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// shortest paths over any state, the neighbours of a state are given by a
+// closure with the cost of the move, the deadline is checked for every
+// visited state
+#[derive(Clone, Debug, Default)]
+pub struct Search {
+    paths: bool,
+}
+
+// the first goal reached, visited counts the states taken out of the queue
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub state: S,
+    pub cost: C,
+    pub visited: usize,
+    // from the start to the goal included, when asked for
+    pub path: Option<Vec<S>>,
+}
+
+// the states in the order they are discovered, with their best cost and the
+// index of the state they are reached from
+struct Discovered<S, C> {
+    index: HashMap<S, usize>,
+    states: Vec<(S, C, usize)>,
+}
+
+impl<S, C> Discovered<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    fn new(start: S, cost: C) -> Self {
+        Discovered {
+            index: HashMap::from([(start.clone(), 0)]),
+            states: vec![(start, cost, 0)],
+        }
+    }
+
+    // the index of state when it is new or reached for less than before
+    fn improve(&mut self, state: S, cost: C, parent: usize) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                let best = &mut self.states[i];
+
+                (cost < best.1).then(|| {
+                    best.1 = cost;
+                    best.2 = parent;
+                    i
+                })
+            }
+            Entry::Vacant(entry) => {
+                let i = self.states.len();
+
+                self.states.push((entry.key().clone(), cost, parent));
+                entry.insert(i);
+                Some(i)
+            }
+        }
+    }
+
+    fn found(&self, i: usize, visited: usize, paths: bool) -> Found<S, C> {
+        let path = paths.then(|| {
+            let mut path = vec![self.states[i].0.clone()];
+            let mut i = i;
+
+            while i != 0 {
+                i = self.states[i].2;
+                path.push(self.states[i].0.clone());
+            }
+            path.reverse();
+            path
+        });
+        let (state, cost, _) = self.states[i].clone();
+
+        Found {
+            state,
+            cost,
+            visited,
+            path,
+        }
+    }
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // keep track of the predecessors to give the path to the goal
+    pub fn paths(self) -> Self {
+        Search { paths: true }
+    }
+
+    // https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm#Using_a_priority_queue
+    pub fn dijkstra<S, C, N, I>(
+        &self,
+        start: S,
+        neighbours: N,
+        goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<Found<S, C>>>
+    where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Add<Output = C> + Default,
+        N: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        self.astar(start, neighbours, |_| C::default(), goal)
+    }
+
+    // the heuristic must never overestimate the cost left to reach a goal
+    // https://en.wikipedia.org/wiki/A*_search_algorithm
+    pub fn astar<S, C, N, I>(
+        &self,
+        start: S,
+        mut neighbours: N,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<Found<S, C>>>
+    where
+        S: Clone + Eq + Hash,
+        C: Copy + Ord + Add<Output = C> + Default,
+        N: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        let mut heap = BinaryHeap::new();
+        let mut discovered = Discovered::new(start, C::default());
+        let mut visited = 0;
+
+        heap.push(Reverse((C::default(), C::default(), 0)));
+
+        while let Some(Reverse((_, cost, i))) = heap.pop() {
+            // a better way was found since it was queued
+            if cost > discovered.states[i].1 {
+                continue;
+            }
+
+            check_deadline()?;
+            visited += 1;
+
+            let state = discovered.states[i].0.clone();
+
+            if goal(&state) {
+                return Ok(Some(discovered.found(i, visited, self.paths)));
+            }
+
+            for (next, step) in neighbours(&state) {
+                let cost = cost + step;
+                let estimate = cost + heuristic(&next);
+
+                if let Some(j) = discovered.improve(next, cost, i) {
+                    heap.push(Reverse((estimate, cost, j)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    // every move costs 1
+    // https://en.wikipedia.org/wiki/Breadth-first_search
+    pub fn bfs<S, N, I>(
+        &self,
+        start: S,
+        mut neighbours: N,
+        mut goal: impl FnMut(&S) -> bool,
+    ) -> Result<Option<Found<S, usize>>>
+    where
+        S: Clone + Eq + Hash,
+        N: FnMut(&S) -> I,
+        I: IntoIterator<Item = S>,
+    {
+        let mut queue = VecDeque::from([0]);
+        let mut discovered = Discovered::new(start, 0);
+        let mut visited = 0;
+
+        while let Some(i) = queue.pop_front() {
+            check_deadline()?;
+            visited += 1;
+
+            let (state, cost, _) = discovered.states[i].clone();
+
+            if goal(&state) {
+                return Ok(Some(discovered.found(i, visited, self.paths)));
+            }
+
+            // the first time a state is reached is the shortest
+            for next in neighbours(&state) {
+                if let Some(j) = discovered.improve(next, cost + 1, i) {
+                    queue.push_back(j);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[test]
+fn check_search() {
+    use crate::{Direction, Grid, Point};
+
+    // the cost of a move is the number of the block entered, # are walls
+    let grid = Grid::from_rows(
+        ["1111", "9#91", "1#11", "1111"]
+            .iter()
+            .map(|row| row.bytes().collect())
+            .collect(),
+    )
+    .unwrap();
+    let neighbours = |&p: &Point| {
+        Direction::ALL
+            .map(|d| p + d.vector())
+            .into_iter()
+            .filter(|&n| grid.get(n).is_some_and(|&c| c != b'#'))
+            .map(|n| (n, (grid[n] - b'0') as u32))
+            .collect::<Vec<_>>()
+    };
+    let end = Point::new(0, 3);
+    let goal = |&p: &Point| p == end;
+
+    let found = Search::new()
+        .paths()
+        .dijkstra(Point::ORIGIN, neighbours, goal)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(found.cost, 9);
+    assert_eq!(found.path.as_ref().unwrap().len(), 10);
+    assert_eq!(found.path.as_ref().unwrap().first(), Some(&Point::ORIGIN));
+    assert_eq!(found.path.as_ref().unwrap().last(), Some(&end));
+
+    let astar = Search::new()
+        .astar(
+            Point::ORIGIN,
+            neighbours,
+            |&p| p.manhattan(end) as u32,
+            goal,
+        )
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(astar.cost, 9);
+    assert_eq!(astar.path, None);
+    assert!(astar.visited <= found.visited);
+
+    let bfs = Search::new()
+        .paths()
+        .bfs(
+            Point::ORIGIN,
+            |&p| neighbours(&p).into_iter().map(|(n, _)| n),
+            goal,
+        )
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(bfs.cost, 3);
+    assert_eq!(
+        bfs.path.unwrap(),
+        [(0, 0), (0, 1), (0, 2), (0, 3)].map(Point::from)
+    );
+
+    let walled = Search::new()
+        .bfs(Point::ORIGIN, |_| [], |&p: &Point| p == end)
+        .unwrap();
+
+    assert_eq!(walled, None);
+}
@@ -0,0 +1,112 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+const DAYS: RangeInclusive<u32> = 1..=25;
+
+// a day or a group of days given on the command line
+#[derive(Clone, Debug, PartialEq)]
+pub enum Selection {
+    Days(RangeInclusive<u32>),
+    Implemented,
+    Missing,
+}
+
+fn day(s: &str) -> Result<u32, String> {
+    match s.parse() {
+        Ok(day) if DAYS.contains(&day) => Ok(day),
+        Ok(day) => Err(format!("day {day} is not between 1 and 25")),
+        Err(_) => Err(format!(
+            "invalid day {s}, expected a number, a range (1-10, ..5, 20..), all, implemented or missing"
+        )),
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = |first, last| {
+            if first <= last {
+                Ok(Selection::Days(first..=last))
+            } else {
+                Err(format!("empty range {s}"))
+            }
+        };
+
+        match s {
+            "all" => Ok(Selection::Days(DAYS)),
+            "implemented" => Ok(Selection::Implemented),
+            "missing" => Ok(Selection::Missing),
+            _ => {
+                if let Some(last) = s.strip_prefix("..") {
+                    range(*DAYS.start(), day(last)?)
+                } else if let Some(first) = s.strip_suffix("..") {
+                    range(day(first)?, *DAYS.end())
+                } else if let Some((first, last)) = s.split_once('-') {
+                    range(day(first)?, day(last)?)
+                } else {
+                    day(s).map(|d| Selection::Days(d..=d))
+                }
+            }
+        }
+    }
+}
+
+// the days in the order they are first given, no selection means the
+// implemented ones
+pub fn expand(selections: &[Selection], implemented: &[u32]) -> Vec<u32> {
+    let mut days = vec![];
+
+    let all = [Selection::Implemented];
+    let selections = if selections.is_empty() {
+        &all
+    } else {
+        selections
+    };
+
+    for selection in selections {
+        let selected: Vec<u32> = match selection {
+            Selection::Days(range) => range.clone().collect(),
+            Selection::Implemented => implemented.to_vec(),
+            Selection::Missing => DAYS.filter(|d| !implemented.contains(d)).collect(),
+        };
+
+        for day in selected {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    days
+}
+
+#[test]
+fn check_selection() {
+    let parse = |s: &str| s.parse::<Selection>();
+
+    assert_eq!(parse("7"), Ok(Selection::Days(7..=7)));
+    assert_eq!(parse("1-10"), Ok(Selection::Days(1..=10)));
+    assert_eq!(parse("..5"), Ok(Selection::Days(1..=5)));
+    assert_eq!(parse("20.."), Ok(Selection::Days(20..=25)));
+    assert_eq!(parse("all"), Ok(Selection::Days(1..=25)));
+    assert!(parse("0").is_err());
+    assert!(parse("26").is_err());
+    assert!(parse("5-3").is_err());
+    assert!(parse("x").is_err());
+
+    let implemented = [1, 2, 9];
+
+    assert_eq!(expand(&[], &implemented), vec![1, 2, 9]);
+    assert_eq!(
+        expand(
+            &[Selection::Days(9..=9), Selection::Days(1..=3)],
+            &implemented
+        ),
+        vec![9, 1, 2, 3]
+    );
+    assert_eq!(
+        expand(&[parse("..4").unwrap(), Selection::Missing], &implemented).len(),
+        24
+    );
+}
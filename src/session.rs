@@ -0,0 +1,125 @@
+use crate::error::DownloadError;
+use crate::http;
+use crate::{Config, Result};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// $XDG_CONFIG_HOME/aoc/session, defaulting to ~/.config/aoc/session
+fn session_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("aoc").join("session"))
+}
+
+fn warn_if_readable(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)?.permissions().mode();
+
+        if mode & 0o077 != 0 {
+            eprintln!(
+                "warning: {} is accessible by group or others (mode {:o}), run chmod 600 on it",
+                path.display(),
+                mode & 0o777
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// the file holds the cookie value, optionally prefixed by "session="
+fn read_session_file(path: &Path) -> Result<Option<String>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    warn_if_readable(path)?;
+
+    let token = content.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    Ok(Some(token.to_string()).filter(|t| !t.is_empty()))
+}
+
+// --session, then AOC_SESSION, then the session file
+pub fn session(config: &Config) -> Result<String> {
+    if let Some(session) = &config.session {
+        return Ok(session.clone());
+    }
+
+    if let Some(session) = env::var("AOC_SESSION").ok().filter(|s| !s.is_empty()) {
+        return Ok(session);
+    }
+
+    match session_path() {
+        Some(path) => read_session_file(&path)?.ok_or(DownloadError::NoSession.into()),
+        None => Err(DownloadError::NoSession.into()),
+    }
+}
+
+// the settings page is small and only served to logged in users, the
+// account name is displayed in the page header
+pub fn check(config: &Config, session: &str) -> Result<String> {
+    let url = format!("{}/settings", config.base_url);
+    let response = http::get_uncached(config, &url, session)?;
+
+    if response.status != 200 {
+        return Err(DownloadError::NotLoggedIn.into());
+    }
+
+    let html = response.text();
+    let start = html
+        .find("<div class=\"user\">")
+        .ok_or(DownloadError::NotLoggedIn)?
+        + "<div class=\"user\">".len();
+    let end = start + html[start..].find('<').unwrap_or(0);
+
+    Ok(html[start..end].trim().to_string())
+}
+
+#[test]
+fn check_session_file() {
+    use crate::mock::temp_dir;
+
+    let dir = temp_dir("session");
+    let path = dir.join("session");
+
+    assert_eq!(read_session_file(&path).unwrap(), None);
+
+    fs::write(&path, "session=53616c7465645f5f\n").unwrap();
+
+    assert_eq!(
+        read_session_file(&path).unwrap().as_deref(),
+        Some("53616c7465645f5f")
+    );
+}
+
+#[test]
+fn check_login() {
+    use crate::mock::{temp_dir, MockServer};
+    use crate::Error;
+
+    let server = MockServer::start(vec![
+        (
+            200,
+            "<header><div><div class=\"user\">ccalmels <span class=\"star-count\">38*</span></div></div></header>",
+        ),
+        (302, ""),
+    ]);
+    let config = crate::mock::config(&server.url, &temp_dir("login"));
+
+    assert_eq!(check(&config, "cookie").unwrap(), "ccalmels");
+    assert!(matches!(
+        check(&config, "expired"),
+        Err(Error::Download(DownloadError::NotLoggedIn))
+    ));
+    assert!(server.requests()[1].contains("session=expired"));
+}
@@ -0,0 +1,48 @@
+use crate::{Input, Result, Validator};
+use std::any::Any;
+use std::fmt::Display;
+use std::io::{BufRead, Lines};
+
+// a day split in phases that can be run and timed on their own, both parts
+// sharing the parsed input
+pub trait Solution {
+    type Parsed: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Answer1>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Answer2>;
+
+    // accepts any input unless the day tells otherwise
+    fn validator() -> Validator {
+        Validator::new()
+    }
+
+    fn resolve<T>(lines: Lines<T>) -> Result<(Self::Answer1, Self::Answer2)>
+    where
+        T: BufRead,
+    {
+        let parsed = Self::parse(lines)?;
+
+        Ok((Self::part1(&parsed)?, Self::part2(&parsed)?))
+    }
+}
+
+// the type erased phases stored in a Day, a parsed input is only ever given
+// back to the parts of the solution that produced it
+pub fn parse<S: Solution>(lines: Input) -> Result<Box<dyn Any>> {
+    Ok(Box::new(S::parse(lines)?))
+}
+
+pub fn part1<S: Solution>(parsed: &dyn Any) -> Result<String> {
+    S::part1(parsed.downcast_ref().unwrap()).map(|answer| answer.to_string())
+}
+
+pub fn part2<S: Solution>(parsed: &dyn Any) -> Result<String> {
+    S::part2(parsed.downcast_ref().unwrap()).map(|answer| answer.to_string())
+}
@@ -0,0 +1,309 @@
+use crate::answers::Answers;
+use crate::http;
+use crate::{Config, Error, Result};
+use curl::easy::Easy;
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Right,
+    Wrong(Hint),
+    Wait(u64),
+    AlreadySolved,
+    Refused(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "right answer"),
+            Outcome::Wrong(Hint::TooHigh) => write!(f, "wrong answer (too high)"),
+            Outcome::Wrong(Hint::TooLow) => write!(f, "wrong answer (too low)"),
+            Outcome::Wrong(Hint::None) => write!(f, "wrong answer"),
+            Outcome::Wait(seconds) => write!(f, "answered too recently, wait {seconds}s"),
+            Outcome::AlreadySolved => write!(f, "this part is already solved"),
+            Outcome::Refused(reason) => write!(f, "not submitted: {reason}"),
+        }
+    }
+}
+
+// "You have 1m 23s left to wait."
+fn parse_wait(text: &str) -> Option<u64> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end].split_whitespace().try_fold(0, |total, t| {
+        let (value, unit) = t.split_at(t.len() - 1);
+        let value = value.parse::<u64>().ok()?;
+
+        match unit {
+            "h" => Some(total + 3600 * value),
+            "m" => Some(total + 60 * value),
+            "s" => Some(total + value),
+            _ => None,
+        }
+    })
+}
+
+pub fn parse_outcome(html: &str) -> Option<Outcome> {
+    if html.contains("That's the right answer") {
+        Some(Outcome::Right)
+    } else if html.contains("That's not the right answer") {
+        Some(Outcome::Wrong(if html.contains("too high") {
+            Hint::TooHigh
+        } else if html.contains("too low") {
+            Hint::TooLow
+        } else {
+            Hint::None
+        }))
+    } else if html.contains("You gave an answer too recently") {
+        parse_wait(html).map(Outcome::Wait)
+    } else if html.contains("You don't seem to be solving the right level") {
+        Some(Outcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn check_parse_outcome() {
+    assert_eq!(
+        parse_outcome("<article><p>That's the right answer!  You are one gold star closer"),
+        Some(Outcome::Right)
+    );
+    assert_eq!(
+        parse_outcome("<p>That's not the right answer; your answer is too high.  If you're stuck"),
+        Some(Outcome::Wrong(Hint::TooHigh))
+    );
+    assert_eq!(
+        parse_outcome("<p>That's not the right answer.  If you're stuck, make sure"),
+        Some(Outcome::Wrong(Hint::None))
+    );
+    assert_eq!(
+        parse_outcome("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait."),
+        Some(Outcome::Wait(83))
+    );
+    assert_eq!(
+        parse_outcome(
+            "<p>You don't seem to be solving the right level.  Did you already complete it?"
+        ),
+        Some(Outcome::AlreadySolved)
+    );
+    assert_eq!(parse_outcome("<html>Please log in</html>"), None);
+}
+
+// answers/NN.wrong keeps one "<part> <answer> <high|low|->" line per rejected answer
+struct Wrongs {
+    path: PathBuf,
+    answers: Vec<(u8, String, Hint)>,
+}
+
+impl Wrongs {
+    fn load(dir: &Path, day_number: u32) -> Result<Self> {
+        let path = dir.join(format!("{day_number:0>2}.wrong"));
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let answers = content
+            .lines()
+            .filter_map(|line| {
+                let mut words = line.split_whitespace();
+                let part = words.next()?.parse().ok()?;
+                let answer = words.next()?.to_string();
+                let hint = match words.next()? {
+                    "high" => Hint::TooHigh,
+                    "low" => Hint::TooLow,
+                    _ => Hint::None,
+                };
+
+                Some((part, answer, hint))
+            })
+            .collect();
+
+        Ok(Wrongs { path, answers })
+    }
+
+    fn add(&mut self, part: u8, answer: &str, hint: Hint) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let h = match hint {
+            Hint::TooHigh => "high",
+            Hint::TooLow => "low",
+            Hint::None => "-",
+        };
+
+        writeln!(file, "{part} {answer} {h}")?;
+        self.answers.push((part, answer.to_string(), hint));
+        Ok(())
+    }
+
+    // an answer already rejected, or beyond a known too high/too low bound
+    fn rejects(&self, part: u8, answer: &str) -> Option<String> {
+        let value = answer.parse::<i64>().ok();
+
+        self.answers
+            .iter()
+            .filter(|(p, _, _)| *p == part)
+            .find_map(|(_, wrong, hint)| {
+                if wrong == answer {
+                    return Some(format!("{answer} is already known to be wrong"));
+                }
+
+                let (value, wrong) = (value?, wrong.parse::<i64>().ok()?);
+
+                match hint {
+                    Hint::TooHigh if value >= wrong => {
+                        Some(format!("{answer} is not lower than {wrong} known too high"))
+                    }
+                    Hint::TooLow if value <= wrong => {
+                        Some(format!("{answer} is not higher than {wrong} known too low"))
+                    }
+                    _ => None,
+                }
+            })
+    }
+}
+
+pub fn submit(
+    config: &Config,
+    session: &str,
+    day_number: u32,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome> {
+    let answers_dir = &config.answers_dir;
+    let mut answers = Answers::load(answers_dir, day_number)?;
+    let known = if part == 1 {
+        &answers.part1
+    } else {
+        &answers.part2
+    };
+
+    match known {
+        Some(known) if known == answer => {
+            return Ok(Outcome::Refused(format!(
+                "{answer} is already known to be right"
+            )))
+        }
+        Some(known) => {
+            return Ok(Outcome::Refused(format!(
+                "the right answer is already known to be {known}"
+            )))
+        }
+        None => {}
+    }
+
+    let mut wrongs = Wrongs::load(answers_dir, day_number)?;
+
+    if let Some(reason) = wrongs.rejects(part, answer) {
+        return Ok(Outcome::Refused(reason));
+    }
+
+    let url = format!("{}/answer", config.day_url(day_number));
+    let encoded = Easy::new().url_encode(answer.as_bytes());
+    let response = http::post(
+        config,
+        &url,
+        session,
+        &format!("level={part}&answer={encoded}"),
+    )?;
+
+    if response.status != 200 {
+        return Err(Error::Submit(format!(
+            "{url} answered HTTP {}",
+            response.status
+        )));
+    }
+
+    let html = response.text();
+    let outcome = parse_outcome(&html).ok_or_else(|| {
+        Error::Submit(
+            "unexpected response, is the session token (from --session, AOC_SESSION or the session file) valid?"
+                .into(),
+        )
+    })?;
+
+    match outcome {
+        Outcome::Right => {
+            if part == 1 {
+                answers.part1 = Some(answer.to_string());
+            } else {
+                answers.part2 = Some(answer.to_string());
+            }
+            answers.save(answers_dir, day_number)?;
+        }
+        Outcome::Wrong(hint) => wrongs.add(part, answer, hint)?,
+        _ => {}
+    }
+
+    Ok(outcome)
+}
+
+#[test]
+fn check_submit() {
+    use crate::mock::{temp_dir, MockServer};
+
+    let dir = temp_dir("submit");
+    let server = MockServer::start(vec![
+        (
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        ),
+        (200, "<article><p>That's the right answer!</p></article>"),
+        (200, "<article><p>That's the right answer!</p></article>"),
+    ]);
+    let config = crate::mock::config(&server.url, &dir);
+
+    assert_eq!(
+        submit(&config, "cookie", 5, 2, "10").unwrap(),
+        Outcome::Wrong(Hint::TooLow)
+    );
+    // refused locally, the mock would fail on a third request
+    assert!(matches!(
+        submit(&config, "cookie", 5, 2, "10").unwrap(),
+        Outcome::Refused(_)
+    ));
+    assert!(matches!(
+        submit(&config, "cookie", 5, 2, "8").unwrap(),
+        Outcome::Refused(_)
+    ));
+    assert_eq!(
+        submit(&config, "cookie", 5, 2, "46").unwrap(),
+        Outcome::Right
+    );
+    assert!(matches!(
+        submit(&config, "cookie", 5, 2, "46").unwrap(),
+        Outcome::Refused(_)
+    ));
+    assert_eq!(
+        submit(&config, "cookie", 5, 1, "a&b=c d+e").unwrap(),
+        Outcome::Right
+    );
+
+    let requests = server.requests();
+
+    assert!(requests[0].starts_with("POST /2023/day/5/answer "));
+    assert!(requests[0].contains("session=cookie"));
+    assert!(requests[0].ends_with("level=2&answer=10"));
+    assert!(requests[1].ends_with("level=2&answer=46"));
+    assert!(requests[2].ends_with("level=1&answer=a%26b%3Dc%20d%2Be"));
+    assert_eq!(Answers::load(&dir, 5).unwrap().part2.as_deref(), Some("46"));
+}
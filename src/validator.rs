@@ -0,0 +1,167 @@
+use crate::{Error, Result};
+use regex::Regex;
+
+// the shape of the input a day expects, checked before parsing so that a
+// malformed input is reported with its position rather than as a panic or a
+// wrong answer
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    grid: bool,
+    paragraphs: bool,
+    alphabet: Option<&'static str>,
+    pattern: Option<&'static str>,
+    exactly: Option<(char, usize)>,
+}
+
+fn invalid(line: Option<usize>, column: Option<usize>, reason: String) -> Result<()> {
+    Err(Error::Invalid {
+        line,
+        column,
+        reason,
+    })
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // every row has the same number of columns, in each paragraph when the
+    // input is made of paragraphs
+    pub fn grid(self) -> Self {
+        Validator { grid: true, ..self }
+    }
+
+    // empty lines separate paragraphs, they are not checked
+    pub fn paragraphs(self) -> Self {
+        Validator {
+            paragraphs: true,
+            ..self
+        }
+    }
+
+    pub fn alphabet(self, alphabet: &'static str) -> Self {
+        Validator {
+            alphabet: Some(alphabet),
+            ..self
+        }
+    }
+
+    // a regex every line must match
+    pub fn lines(self, pattern: &'static str) -> Self {
+        Validator {
+            pattern: Some(pattern),
+            ..self
+        }
+    }
+
+    // c appears exactly n times in the whole input
+    pub fn exactly(self, c: char, n: usize) -> Self {
+        Validator {
+            exactly: Some((c, n)),
+            ..self
+        }
+    }
+
+    // lines and columns are 1-based
+    pub fn check(&self, content: &str) -> Result<()> {
+        let pattern = self.pattern.map(|p| Regex::new(p).unwrap());
+        let mut width = None;
+        let mut count = 0;
+
+        if content.lines().next().is_none() {
+            return invalid(None, None, "empty input".to_string());
+        }
+
+        for (index, line) in content.lines().enumerate() {
+            let l = Some(index + 1);
+
+            if self.paragraphs && line.is_empty() {
+                width = None;
+                continue;
+            }
+
+            if let Some(alphabet) = self.alphabet {
+                if let Some((x, c)) = line
+                    .chars()
+                    .enumerate()
+                    .find(|(_, c)| !alphabet.contains(*c))
+                {
+                    return invalid(l, Some(x + 1), format!("unexpected {c:?}"));
+                }
+            }
+
+            if self.grid {
+                let len = line.chars().count();
+
+                match width {
+                    _ if len == 0 => return invalid(l, None, "empty row".to_string()),
+                    None => width = Some(len),
+                    Some(w) if w != len => {
+                        return invalid(
+                            l,
+                            Some(len.min(w) + 1),
+                            format!("row of {len} columns instead of {w}"),
+                        )
+                    }
+                    Some(_) => (),
+                }
+            }
+
+            if let Some(re) = &pattern {
+                if !re.is_match(line) {
+                    return invalid(l, None, format!("expected a line matching {re}"));
+                }
+            }
+
+            if let Some((c, n)) = self.exactly {
+                for (x, _) in line.chars().enumerate().filter(|&(_, x)| x == c) {
+                    count += 1;
+
+                    if count > n {
+                        return invalid(l, Some(x + 1), format!("more than {n} {c:?}"));
+                    }
+                }
+            }
+        }
+
+        match self.exactly {
+            Some((c, n)) if count < n => {
+                invalid(None, None, format!("{count} {c:?} instead of {n}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn check_validator() {
+    let position = |result: Result<()>| match result {
+        Err(Error::Invalid { line, column, .. }) => (line, column),
+        _ => panic!("expected an invalid input"),
+    };
+    let grid = Validator::new().grid().alphabet(".#S").exactly('S', 1);
+
+    assert!(grid.check("..#\n.S.\n").is_ok());
+    assert_eq!(position(grid.check("..#\n.x.\n")), (Some(2), Some(2)));
+    assert_eq!(position(grid.check("..#\n.S\n")), (Some(2), Some(3)));
+    assert_eq!(position(grid.check("S.#\n.S.\n")), (Some(2), Some(2)));
+    assert_eq!(position(grid.check("..#\n...\n")), (None, None));
+    assert_eq!(position(grid.check("")), (None, None));
+    // columns count characters, not bytes
+    assert_eq!(position(grid.check("..#\n.S.\n.é#\n")), (Some(3), Some(2)));
+    assert_eq!(position(grid.check("..#\n.S.\n...#\n")), (Some(3), Some(4)));
+
+    let paragraphs = Validator::new().grid().paragraphs();
+
+    assert!(paragraphs.check("..\n##\n\n...\n").is_ok());
+    assert_eq!(
+        position(paragraphs.check("..\n#\n\n...\n")),
+        (Some(2), Some(2))
+    );
+
+    let lines = Validator::new().lines(r"^[RL]+$");
+
+    assert!(lines.check("RL\nLLR").is_ok());
+    assert_eq!(position(lines.check("RL\nLxR")), (Some(2), None));
+}
@@ -0,0 +1,27 @@
+use advent_2023::{Result, Solution};
+use std::io::{BufRead, Lines};
+
+struct Day{{DAY}};
+
+impl Solution for Day{{DAY}} {
+    type Parsed = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<T>(lines: Lines<T>) -> Result<Self::Parsed>
+    where
+        T: BufRead,
+    {
+        Ok(lines.collect::<std::io::Result<_>>()?)
+    }
+
+    fn part1(_lines: &Self::Parsed) -> Result<u64> {
+        Ok(0)
+    }
+
+    fn part2(_lines: &Self::Parsed) -> Result<u64> {
+        Ok(0)
+    }
+}
+
+inventory::submit! { advent_2023::Day::new::<Day{{DAY}}>(file!()) }